@@ -0,0 +1,93 @@
+//! Replays the stream loop's hot path: 100 pods pushing lines through the
+//! worker channel, drained in batches, run through the line pipeline (error
+//! pattern, stats, filters, history) and rendered through a buffered writer.
+//! Exits non-zero if throughput drops below the documented 50k lines/s.
+
+use klog::commands::log::Patterns;
+use klog::commands::log::pipeline::Pipeline;
+use klog::commands::log::render;
+use klog::commands::log::stats::{StreamStats, Thresholds};
+use klog::models::{LogMessage, StreamEvent};
+use regex::Regex;
+use std::io::{BufWriter, Write};
+use std::time::{Duration, Instant};
+
+const PODS: usize = 100;
+const LINES_PER_POD: usize = 5_000;
+const TARGET_LINES_PER_SEC: f64 = 50_000.0;
+
+fn main() {
+    // Measure the real formatting cost, not the no-tty shortcut
+    colored::control::set_override(true);
+
+    // The log command's defaults, plus an exclude so some lines take the hidden path
+    let patterns = Patterns {
+        filter: None,
+        exclude: Some(Regex::new("GET /healthz").unwrap()),
+        error: Regex::new(r"(?i)\b(error|fatal|panic|exception)\b").unwrap(),
+    };
+    let stats = StreamStats::new(Thresholds {
+        spike_errors: 10,
+        spike_window: Duration::from_secs(10),
+        silence: Some(Duration::from_secs(30)),
+    });
+    let mut pipeline = Pipeline::new(patterns, stats);
+
+    let rt = tokio::runtime::Builder::new_multi_thread().enable_all().build().unwrap();
+    let (lines, shown, elapsed) = rt.block_on(async {
+        let (tx, mut rx) = tokio::sync::mpsc::channel(render::CHANNEL_DEPTH);
+        for pod in 0..PODS {
+            let tx = tx.clone();
+            tokio::spawn(async move {
+                for n in 0..LINES_PER_POD {
+                    let message = match n % 50 {
+                        0 => format!("{{\"level\":\"error\",\"seq\":{n},\"msg\":\"upstream timeout\"}}"),
+                        1..=4 => format!("GET /healthz 200 seq={n}"),
+                        _ => format!("{{\"level\":\"info\",\"seq\":{n},\"msg\":\"handled request\",\"latency_ms\":12}}"),
+                    };
+                    let msg = LogMessage {
+                        cluster: None,
+                        namespace: "shop".to_string(),
+                        pod_name: format!("checkout-7d9f8b6c5-{pod:05}"),
+                        container_name: "api".to_string(),
+                        message,
+                    };
+                    if tx.send(StreamEvent::Line(msg)).await.is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(tx);
+
+        let start = Instant::now();
+        let mut batch = Vec::with_capacity(render::BATCH_MAX);
+        let (mut lines, mut shown) = (0usize, 0usize);
+        while rx.recv_many(&mut batch, render::BATCH_MAX).await > 0 {
+            let now = Instant::now();
+            let mut out = BufWriter::with_capacity(render::OUT_BUFFER, std::io::sink());
+            for event in batch.drain(..) {
+                let StreamEvent::Line(log) = event else { continue };
+                lines += 1;
+                let (alerts, visible) = pipeline.ingest(log, false, now);
+                std::hint::black_box(alerts);
+                if let Some(log) = visible {
+                    render::write_line(&mut out, log).unwrap();
+                    shown += 1;
+                }
+            }
+            out.flush().unwrap();
+        }
+        (lines, shown, start.elapsed())
+    });
+
+    let rate = lines as f64 / elapsed.as_secs_f64();
+    println!(
+        "render: {} lines ({} shown) from {} pods in {:.3}s -> {:.0} lines/s (target {:.0})",
+        lines, shown, PODS, elapsed.as_secs_f64(), rate, TARGET_LINES_PER_SEC
+    );
+    if rate < TARGET_LINES_PER_SEC {
+        eprintln!("render throughput below target");
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,80 @@
+use colored::*;
+use comfy_table::Table;
+use k8s_openapi::api::core::v1::{ContainerStatus, Pod};
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::Time;
+
+// One row per container, init containers first, in spec order. Containers the
+// kubelet hasn't reported on yet (e.g. while scheduling) show as pending.
+pub fn table(pod: &Pod) -> Table {
+    let mut table = Table::new();
+    table.set_header(vec![
+        "Container", "State", "Ready", "Restarts", "Started", "Last Termination", "Exit Code", "Signal", "Finished",
+    ]);
+
+    let spec = pod.spec.as_ref();
+    let status = pod.status.as_ref();
+    let init_names = spec.and_then(|s| s.init_containers.as_ref()).into_iter().flatten().map(|c| &c.name);
+    let names = spec.into_iter().flat_map(|s| &s.containers).map(|c| &c.name);
+    let init_statuses = status.and_then(|s| s.init_container_statuses.as_deref()).unwrap_or_default();
+    let statuses = status.and_then(|s| s.container_statuses.as_deref()).unwrap_or_default();
+
+    let rows = init_names
+        .map(|n| (n, true, init_statuses.iter().find(|s| &s.name == n)))
+        .chain(names.map(|n| (n, false, statuses.iter().find(|s| &s.name == n))));
+    for (name, init, cs) in rows {
+        let label = if init { format!("{} (init)", name) } else { name.clone() };
+        match cs {
+            Some(cs) => table.add_row(container_row(label, cs)),
+            None => table.add_row(vec![label, "Pending".yellow().to_string()]),
+        };
+    }
+    table
+}
+
+fn container_row(label: String, cs: &ContainerStatus) -> Vec<String> {
+    let state = cs.state.as_ref();
+    let (state_text, started) = if let Some(running) = state.and_then(|s| s.running.as_ref()) {
+        ("Running".green().to_string(), running.started_at.as_ref())
+    } else if let Some(waiting) = state.and_then(|s| s.waiting.as_ref()) {
+        let reason = waiting.reason.clone().unwrap_or_else(|| "Waiting".to_string());
+        let styled = if reason == "CrashLoopBackOff" { reason.red().bold() } else { reason.yellow() };
+        (styled.to_string(), None)
+    } else if let Some(terminated) = state.and_then(|s| s.terminated.as_ref()) {
+        let reason = terminated.reason.as_deref().unwrap_or("Unknown");
+        let text = format!("Terminated ({})", reason);
+        // OOMKilled stands out like CrashLoopBackOff; other failures are plain red
+        let styled = match (reason, terminated.exit_code) {
+            ("OOMKilled", _) => text.red().bold(),
+            (_, 0) => text.normal(),
+            _ => text.red(),
+        };
+        (styled.to_string(), terminated.started_at.as_ref())
+    } else {
+        ("Unknown".dimmed().to_string(), None)
+    };
+
+    // The current termination if the container is stopped, otherwise the previous run's
+    let terminated = state
+        .and_then(|s| s.terminated.as_ref())
+        .or_else(|| cs.last_state.as_ref().and_then(|s| s.terminated.as_ref()));
+    let reason = terminated.and_then(|t| t.reason.clone()).unwrap_or_default();
+    let reason = if reason == "OOMKilled" { reason.red().bold().to_string() } else { reason };
+
+    let ready = if cs.ready { "Yes".green() } else { "No".red() };
+    let restarts = if cs.restart_count > 0 { cs.restart_count.to_string().yellow() } else { "0".normal() };
+    vec![
+        label,
+        state_text,
+        ready.to_string(),
+        restarts.to_string(),
+        format_time(started),
+        reason,
+        terminated.map(|t| t.exit_code.to_string()).unwrap_or_default(),
+        terminated.and_then(|t| t.signal).map(|s| s.to_string()).unwrap_or_default(),
+        format_time(terminated.and_then(|t| t.finished_at.as_ref())),
+    ]
+}
+
+fn format_time(time: Option<&Time>) -> String {
+    time.map(|t| t.0.strftime("%Y-%m-%d %H:%M:%S").to_string()).unwrap_or_default()
+}
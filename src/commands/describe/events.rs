@@ -0,0 +1,182 @@
+use crate::session::Session;
+use colored::*;
+use comfy_table::Table;
+use indicatif::ProgressBar;
+use jiff::Timestamp;
+use k8s_openapi::api::core::v1 as core;
+use k8s_openapi::api::events::v1 as events;
+use kube::{Api, api::ListParams};
+use std::collections::HashMap;
+
+/// One event about the pod, from either events API, with the fields that
+/// moved between them (count, times, source) resolved.
+#[derive(Clone, Debug)]
+pub struct EventRecord {
+    /// The object's uid (its name if the server left that out); the same in both APIs
+    pub id: String,
+    pub warning: bool,
+    pub reason: String,
+    pub message: String,
+    pub count: i32,
+    pub source: String,
+    pub first: Option<Timestamp>,
+    pub last: Option<Timestamp>,
+}
+
+impl From<core::Event> for EventRecord {
+    fn from(e: core::Event) -> Self {
+        let series = e.series.as_ref();
+        let last = e
+            .last_timestamp
+            .map(|t| t.0)
+            .or_else(|| series.and_then(|s| s.last_observed_time.as_ref()).map(|t| t.0))
+            .or_else(|| e.event_time.as_ref().map(|t| t.0));
+        let source = e.source.as_ref().and_then(|s| s.component.clone()).or(e.reporting_component);
+        Self {
+            id: e.metadata.uid.or(e.metadata.name).unwrap_or_default(),
+            warning: e.type_.as_deref() == Some("Warning"),
+            reason: e.reason.unwrap_or_default(),
+            message: e.message.unwrap_or_default(),
+            count: e.count.or_else(|| series.and_then(|s| s.count)).unwrap_or(1),
+            source: source.unwrap_or_default(),
+            first: e.first_timestamp.map(|t| t.0).or_else(|| e.event_time.map(|t| t.0)).or(last),
+            last,
+        }
+    }
+}
+
+impl From<events::Event> for EventRecord {
+    fn from(e: events::Event) -> Self {
+        let first = e.deprecated_first_timestamp.map(|t| t.0).or_else(|| e.event_time.as_ref().map(|t| t.0));
+        let last = e
+            .series
+            .as_ref()
+            .map(|s| s.last_observed_time.0)
+            .or_else(|| e.deprecated_last_timestamp.map(|t| t.0))
+            .or(first);
+        let source = e.reporting_controller.or_else(|| e.deprecated_source.and_then(|s| s.component));
+        Self {
+            id: e.metadata.uid.or(e.metadata.name).unwrap_or_default(),
+            warning: e.type_.as_deref() == Some("Warning"),
+            reason: e.reason.unwrap_or_default(),
+            message: e.note.unwrap_or_default(),
+            count: e.series.map(|s| s.count).or(e.deprecated_count).unwrap_or(1),
+            source: source.unwrap_or_default(),
+            first,
+            last,
+        }
+    }
+}
+
+// --- FETCHING ---
+/// Events about `pod` from core/v1 and/or events.k8s.io/v1, whichever RBAC
+/// allows. Both APIs serve the same objects, so they are merged by uid with the
+/// newer API winning; events only written through it still show up.
+pub async fn fetch(
+    session: &Session,
+    namespace: &str,
+    pod: &str,
+    (core_allowed, new_allowed): (bool, bool),
+    spinner: (&ProgressBar, &str),
+) -> Result<Vec<EventRecord>, kube::Error> {
+    let mut by_id: HashMap<String, EventRecord> = HashMap::new();
+    if core_allowed {
+        let api: Api<core::Event> = Api::namespaced(session.client.clone(), namespace);
+        let lp = ListParams::default().fields(&format!("involvedObject.kind=Pod,involvedObject.name={}", pod));
+        for e in session.retry.call(Some(spinner), || api.list(&lp)).await?.items {
+            let record = EventRecord::from(e);
+            by_id.insert(record.id.clone(), record);
+        }
+    }
+    if new_allowed {
+        let api: Api<events::Event> = Api::namespaced(session.client.clone(), namespace);
+        let lp = ListParams::default().fields(&format!("regarding.kind=Pod,regarding.name={}", pod));
+        let items = match session.retry.call(Some(spinner), || api.list(&lp)).await {
+            Ok(list) => list.items,
+            // Older clusters may not serve it; the core list is enough there
+            Err(kube::Error::Api(status)) if status.code == 404 => Vec::new(),
+            Err(e) => return Err(e),
+        };
+        for e in items {
+            let record = EventRecord::from(e);
+            by_id.insert(record.id.clone(), record);
+        }
+    }
+    let mut records: Vec<EventRecord> = by_id.into_values().collect();
+    // Newest first
+    records.sort_by_key(|r| std::cmp::Reverse(r.last));
+    Ok(records)
+}
+
+// --- GROUPING ---
+// Repeats of the same type/reason/message/source (e.g. one per restart) become
+// one row with their counts added up and the widest time span. Keeps newest-first order.
+fn group(records: &[EventRecord]) -> Vec<EventRecord> {
+    let mut grouped: Vec<EventRecord> = Vec::new();
+    for r in records {
+        match grouped
+            .iter_mut()
+            .find(|g| g.warning == r.warning && g.reason == r.reason && g.message == r.message && g.source == r.source)
+        {
+            Some(g) => {
+                g.count += r.count;
+                g.first = match (g.first, r.first) {
+                    (Some(a), Some(b)) => Some(a.min(b)),
+                    (a, b) => a.or(b),
+                };
+                g.last = g.last.max(r.last);
+            }
+            None => grouped.push(r.clone()),
+        }
+    }
+    grouped
+}
+
+// --- TABLE ---
+/// The `limit` most recent (grouped) events, newest first.
+pub fn print(records: &[EventRecord], limit: usize, warnings_only: bool) {
+    let shown: Vec<EventRecord> = group(records).into_iter().filter(|r| r.warning || !warnings_only).collect();
+    if shown.is_empty() {
+        let what = if warnings_only { "warning events" } else { "recent events" };
+        println!("   (No {})", what);
+        return;
+    }
+
+    let now = Timestamp::now();
+    let mut table = Table::new();
+    table.set_header(vec!["Type", "Reason", "Age", "Count", "From", "Message"]);
+    for r in shown.iter().take(limit) {
+        let row_type = if r.warning { "Warning".red() } else { "Normal".green() };
+        // Like kubectl: "3m" for a one-off, "3m (over 2h)" for a repeat
+        let seen = match (r.first, r.last) {
+            (Some(first), Some(last)) if r.count > 1 && first < last => {
+                format!("{} (over {})", age(now, last), age(last, first))
+            }
+            (_, Some(last)) => age(now, last),
+            _ => "-".to_string(),
+        };
+        table.add_row(vec![
+            row_type.to_string(),
+            r.reason.clone(),
+            seen,
+            r.count.to_string(),
+            r.source.clone(),
+            r.message.clone(),
+        ]);
+    }
+    println!("{table}");
+    if shown.len() > limit {
+        println!("   {}", format!("({} older events not shown, see --events N)", shown.len() - limit).dimmed());
+    }
+}
+
+// Time from `then` to `now`, in the largest unit that fits: 45s, 12m, 3h, 2d
+fn age(now: Timestamp, then: Timestamp) -> String {
+    let secs = now.as_second().saturating_sub(then.as_second()).max(0);
+    match secs {
+        s if s < 60 => format!("{}s", s),
+        s if s < 3600 => format!("{}m", s / 60),
+        s if s < 86400 => format!("{}h", s / 3600),
+        s => format!("{}d", s / 86400),
+    }
+}
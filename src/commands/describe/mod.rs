@@ -0,0 +1,110 @@
+mod containers;
+mod events;
+mod resources;
+mod triage;
+
+use crate::models::PodOption;
+use crate::rbac::{self, Permission};
+use crate::session::Session;
+use crate::utils;
+use kube::Api;
+use k8s_openapi::api::core::v1::Pod;
+use colored::*;
+use comfy_table::Table;
+
+pub async fn run(
+    session: &Session,
+    pod_arg: Option<String>,
+    namespace_arg: Option<Option<String>>,
+    event_limit: usize,
+    warnings_only: bool,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+
+    // 1. Get the target pod (reusing your utils)
+    let target = match pod_arg {
+        Some(name) => {
+            let ns = utils::get_selected_namespaces(session, namespace_arg).await?;
+            PodOption { name, namespace: ns[0].clone(), containers: vec![], cluster: None }
+        },
+        None => {
+            let ns = utils::get_selected_namespaces(session, namespace_arg).await?;
+            let perms: Vec<Permission> = ns.iter().map(|n| Permission::new("list", "pods", Some(n))).collect();
+            rbac::require(session, &perms).await?;
+            let pods = utils::fetch_all_pods(session, ns).await?;
+            inquire::Select::new("Select pod to describe:", pods).prompt()?
+        }
+    };
+
+    // 2. Fetch Data (Pod + Events)
+    // The pod is required; events are a bonus we skip if RBAC hides them
+    let ns = Some(target.namespace.as_str());
+    rbac::require(session, &[Permission::new("get", "pods", ns)]).await?;
+    let list_events = Permission::new("list", "events", ns);
+    let list_new_events = Permission::new("list", "events.events.k8s.io", ns);
+    let denied = rbac::missing(session, &[list_events.clone(), list_new_events.clone()]).await;
+    let allowed = |perm: &Permission| !denied.iter().any(|d| d.resource == perm.resource);
+    let events_allowed = (allowed(&list_events), allowed(&list_new_events));
+
+    let pod_api: Api<Pod> = Api::namespaced(session.client.clone(), &target.namespace);
+    
+    let label = format!("Fetching {}...", target.name);
+    let pb = utils::create_spinner(&label);
+    let p = session.retry.call(Some((&pb, &label)), || pod_api.get(&target.name)).await?;
+    
+    // Events specifically for this pod, from whichever events API we may read
+    let records = match events_allowed {
+        (false, false) => None,
+        allowed => Some(events::fetch(session, &target.namespace, &target.name, allowed, (&pb, &label)).await?),
+    };
+    pb.finish_and_clear();
+
+    // --- 3. PRINT VITAL SIGNS ---
+    println!("\n{}", "--- POD VITAL SIGNS ---".bold().bright_white());
+    
+    let mut vitals = Table::new();
+    vitals.set_header(vec!["Property", "Value"]);
+
+    // Status & Age
+    let status = p.status.as_ref().and_then(|s| s.phase.clone()).unwrap_or_default();
+    let color_status = if status == "Running" { status.green() } else { status.red() };
+    vitals.add_row(vec!["Status", &color_status.to_string()]);
+    
+    if let Some(ip) = p.status.as_ref().and_then(|s| s.pod_ip.clone()) {
+        vitals.add_row(vec!["Pod IP", &ip]);
+    }
+
+    vitals.add_row(vec!["QoS Class", &resources::qos_class(&p)]);
+    println!("{vitals}");
+
+    // --- 4. PRINT CONTAINERS ---
+    println!("\n{}", "--- CONTAINERS ---".bold().bright_white());
+    println!("{}", containers::table(&p));
+
+    // --- 5. PRINT RESOURCES ---
+    println!("\n{}", "--- RESOURCES ---".bold().bright_white());
+    println!("{}", resources::table(&p));
+    for warning in resources::warnings(&p) {
+        println!("{} {}", "⚠️ ".yellow(), warning.yellow());
+    }
+
+    // --- 6. PRINT RECENT EVENTS ---
+    println!("\n{}", "--- RECENT EVENTS ---".bold().bright_white());
+    match &records {
+        Some(records) => events::print(records, event_limit, warnings_only),
+        // Only hidden when both events APIs are denied
+        None => println!(
+            "   (Events hidden: missing permissions `{}` and `{}`)",
+            list_events.to_string().yellow(),
+            list_new_events.to_string().yellow()
+        ),
+    }
+
+    // --- 7. PRINT FINDINGS ---
+    println!("\n{}", "--- FINDINGS ---".bold().bright_white());
+    triage::print(&triage::diagnose(&p, records.as_deref().unwrap_or_default()));
+    if records.is_none() {
+        println!("   {}", "(Event-based checks such as failing probes were skipped)".dimmed());
+    }
+
+    Ok(())
+}
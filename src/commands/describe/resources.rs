@@ -0,0 +1,351 @@
+use colored::*;
+use comfy_table::Table;
+use k8s_openapi::api::core::v1::{Container, Pod};
+use k8s_openapi::apimachinery::pkg::api::resource::Quantity;
+use std::collections::BTreeMap;
+
+const CPU: &str = "cpu";
+const MEMORY: &str = "memory";
+// A limit this many times its request gets flagged
+const OVERCOMMIT_RATIO: f64 = 4.0;
+
+// Requests and limits of one container as numbers (cores, bytes, units).
+// Like the API server, a missing request defaults to the limit.
+struct Amounts {
+    requests: BTreeMap<String, f64>,
+    limits: BTreeMap<String, f64>,
+}
+
+impl Amounts {
+    fn of(c: &Container) -> Self {
+        let parse = |m: Option<&BTreeMap<String, Quantity>>| {
+            m.into_iter()
+                .flatten()
+                .filter_map(|(k, v)| parse_quantity(&v.0).map(|n| (k.clone(), n)))
+                .collect::<BTreeMap<_, _>>()
+        };
+        let resources = c.resources.as_ref();
+        let limits = parse(resources.and_then(|r| r.limits.as_ref()));
+        let mut requests = parse(resources.and_then(|r| r.requests.as_ref()));
+        for (k, v) in &limits {
+            requests.entry(k.clone()).or_insert(*v);
+        }
+        Self { requests, limits }
+    }
+}
+
+// Init containers with restartPolicy Always are sidecars: they keep running next to the app
+fn is_sidecar(c: &Container) -> bool {
+    c.restart_policy.as_deref() == Some("Always")
+}
+
+fn init_containers(pod: &Pod) -> &[Container] {
+    pod.spec.as_ref().and_then(|s| s.init_containers.as_deref()).unwrap_or_default()
+}
+
+fn app_containers(pod: &Pod) -> &[Container] {
+    pod.spec.as_ref().map(|s| s.containers.as_slice()).unwrap_or_default()
+}
+
+// --- TABLE ---
+/// Requests and limits per container as written in the spec, then the pod's
+/// effective totals, i.e. what the scheduler reserves.
+pub fn table(pod: &Pod) -> Table {
+    let mut table = Table::new();
+    table.set_header(vec!["Container", "CPU Req", "CPU Lim", "Mem Req", "Mem Lim", "Other (req/lim)"]);
+
+    let rows = init_containers(pod).iter().map(|c| (c, true)).chain(app_containers(pod).iter().map(|c| (c, false)));
+    for (c, init) in rows {
+        let resources = c.resources.as_ref();
+        let get = |limit: bool, name: &str| {
+            let map = resources.and_then(|r| if limit { r.limits.as_ref() } else { r.requests.as_ref() });
+            map.and_then(|m| m.get(name)).map(|q| q.0.clone())
+        };
+        let show = |v: Option<String>| v.unwrap_or_else(|| "-".dimmed().to_string());
+        let extended: Vec<String> = extended_names(resources.and_then(|r| r.requests.as_ref()), resources.and_then(|r| r.limits.as_ref()))
+            .into_iter()
+            .map(|name| format!("{} {}/{}", name, show(get(false, &name)), show(get(true, &name))))
+            .collect();
+        let label = if init { format!("{} (init)", c.name) } else { c.name.clone() };
+        table.add_row(vec![
+            label,
+            show(get(false, CPU)),
+            show(get(true, CPU)),
+            show(get(false, MEMORY)),
+            show(get(true, MEMORY)),
+            extended.join(", "),
+        ]);
+    }
+
+    let (requests, limits) = effective(pod);
+    let total = |map: &BTreeMap<String, Option<f64>>, name: &str| match map.get(name) {
+        Some(Some(v)) => format_amount(name, *v),
+        Some(None) => "unbounded".yellow().to_string(),
+        None => "-".dimmed().to_string(),
+    };
+    // Both maps have a key for every resource any container mentions
+    let extended: Vec<String> = requests
+        .keys()
+        .filter(|k| *k != CPU && *k != MEMORY)
+        .map(|name| format!("{} {}/{}", name, total(&requests, name), total(&limits, name)))
+        .collect();
+    table.add_row(vec![
+        "Pod (effective)".bold().to_string(),
+        total(&requests, CPU),
+        total(&limits, CPU),
+        total(&requests, MEMORY),
+        total(&limits, MEMORY),
+        extended.join(", "),
+    ]);
+    table
+}
+
+fn extended_names(
+    requests: Option<&BTreeMap<String, Quantity>>,
+    limits: Option<&BTreeMap<String, Quantity>>,
+) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for name in requests.into_iter().flatten().chain(limits.into_iter().flatten()).map(|(k, _)| k) {
+        if name != CPU && name != MEMORY && !names.contains(name) {
+            names.push(name.clone());
+        }
+    }
+    names
+}
+
+// The pod's effective request/limit per resource: the larger of what the app
+// containers (plus sidecars) use together and what the biggest init container
+// uses on its own. A CPU/memory limit is None (unbounded) when a running container has none.
+fn effective(pod: &Pod) -> (BTreeMap<String, Option<f64>>, BTreeMap<String, Option<f64>>) {
+    let running: Vec<Amounts> = init_containers(pod)
+        .iter()
+        .filter(|c| is_sidecar(c))
+        .chain(app_containers(pod))
+        .map(Amounts::of)
+        .collect();
+    let inits: Vec<Amounts> = init_containers(pod).iter().filter(|c| !is_sidecar(c)).map(Amounts::of).collect();
+
+    let names: Vec<String> = running
+        .iter()
+        .chain(&inits)
+        .flat_map(|a| a.requests.keys().chain(a.limits.keys()))
+        .fold(Vec::new(), |mut names, k| {
+            if !names.contains(k) {
+                names.push(k.clone());
+            }
+            names
+        });
+
+    let mut requests = BTreeMap::new();
+    let mut limits = BTreeMap::new();
+    let init_max = |pick: fn(&Amounts) -> &BTreeMap<String, f64>, name: &str| {
+        inits.iter().filter_map(|a| pick(a).get(name)).fold(0.0_f64, |m, v| m.max(*v))
+    };
+    for name in names {
+        let sum: f64 = running.iter().filter_map(|a| a.requests.get(&name)).sum();
+        requests.insert(name.clone(), Some(sum.max(init_max(|a| &a.requests, &name))));
+
+        // Extended resources are only used by containers that ask for them
+        let bounded_by_default = name != CPU && name != MEMORY;
+        let unbounded = !bounded_by_default && running.iter().any(|a| !a.limits.contains_key(&name));
+        let sum: f64 = running.iter().filter_map(|a| a.limits.get(&name)).sum();
+        let limit = (!unbounded).then(|| sum.max(init_max(|a| &a.limits, &name)));
+        limits.insert(name, limit);
+    }
+    (requests, limits)
+}
+
+// --- QOS CLASS ---
+/// The class the kubelet assigned (status.qosClass). Only when the status lacks
+/// it is it derived from the spec: Guaranteed when every container's CPU and
+/// memory limits equal its requests, BestEffort when no container sets any,
+/// Burstable otherwise.
+pub fn qos_class(pod: &Pod) -> String {
+    if let Some(class) = pod.status.as_ref().and_then(|s| s.qos_class.clone()) {
+        return class;
+    }
+    let all: Vec<Amounts> = init_containers(pod).iter().chain(app_containers(pod)).map(Amounts::of).collect();
+    let sets_any = all.iter().any(|a| [CPU, MEMORY].iter().any(|r| a.requests.contains_key(*r) || a.limits.contains_key(*r)));
+    if !sets_any {
+        return "BestEffort".to_string();
+    }
+    let guaranteed = all.iter().all(|a| {
+        [CPU, MEMORY].iter().all(|r| match (a.requests.get(*r), a.limits.get(*r)) {
+            (Some(req), Some(lim)) => req == lim,
+            _ => false,
+        })
+    });
+    if guaranteed { "Guaranteed" } else { "Burstable" }.to_string()
+}
+
+// --- WARNINGS ---
+/// Containers without a memory limit, or with a CPU/memory limit far above the request.
+/// Only app containers and sidecars count: regular init containers have finished
+/// before the app starts, so their limits don't matter for long.
+pub fn warnings(pod: &Pod) -> Vec<String> {
+    let mut warnings = Vec::new();
+    for c in init_containers(pod).iter().filter(|c| is_sidecar(c)).chain(app_containers(pod)) {
+        let amounts = Amounts::of(c);
+        if !amounts.limits.contains_key(MEMORY) {
+            warnings.push(format!("{}: no memory limit, it can use all of the node's free memory", c.name));
+        }
+        for name in [CPU, MEMORY] {
+            if let (Some(req), Some(lim)) = (amounts.requests.get(name), amounts.limits.get(name))
+                && *req > 0.0
+                && lim / req >= OVERCOMMIT_RATIO
+            {
+                warnings.push(format!(
+                    "{}: {} limit {} is {:.0}x its request {}",
+                    c.name,
+                    name,
+                    format_amount(name, *lim),
+                    lim / req,
+                    format_amount(name, *req)
+                ));
+            }
+        }
+    }
+    warnings
+}
+
+// --- QUANTITIES ---
+// Kubernetes quantities: "250m", "1.5", "128Mi", "1G", "1e3"
+fn parse_quantity(q: &str) -> Option<f64> {
+    const SUFFIXES: [(&str, f64); 15] = [
+        ("Ki", 1024.0),
+        ("Mi", 1048576.0),
+        ("Gi", 1073741824.0),
+        ("Ti", 1099511627776.0),
+        ("Pi", 1125899906842624.0),
+        ("Ei", 1152921504606846976.0),
+        ("n", 1e-9),
+        ("u", 1e-6),
+        ("m", 1e-3),
+        ("k", 1e3),
+        ("M", 1e6),
+        ("G", 1e9),
+        ("T", 1e12),
+        ("P", 1e15),
+        ("E", 1e18),
+    ];
+    let q = q.trim();
+    for (suffix, factor) in SUFFIXES {
+        if let Some(number) = q.strip_suffix(suffix)
+            && let Ok(n) = number.parse::<f64>()
+        {
+            return Some(n * factor);
+        }
+    }
+    q.parse().ok()
+}
+
+fn format_amount(name: &str, value: f64) -> String {
+    match name {
+        CPU if value.fract() != 0.0 => format!("{}m", (value * 1000.0).round()),
+        MEMORY => {
+            let units = [("Gi", 1073741824.0), ("Mi", 1048576.0), ("Ki", 1024.0)];
+            match units.iter().find(|(_, size)| value >= *size) {
+                Some((unit, size)) => format!("{}{}", trim_number(value / size), unit),
+                None => trim_number(value),
+            }
+        }
+        _ => trim_number(value),
+    }
+}
+
+fn trim_number(n: f64) -> String {
+    let text = format!("{:.1}", n);
+    text.strip_suffix(".0").unwrap_or(&text).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k8s_openapi::api::core::v1::{PodSpec, PodStatus, ResourceRequirements};
+
+    fn container(name: &str, requests: &[(&str, &str)], limits: &[(&str, &str)], sidecar: bool) -> Container {
+        let map = |pairs: &[(&str, &str)]| {
+            (!pairs.is_empty()).then(|| pairs.iter().map(|(k, v)| (k.to_string(), Quantity(v.to_string()))).collect())
+        };
+        Container {
+            name: name.to_string(),
+            restart_policy: sidecar.then(|| "Always".to_string()),
+            resources: Some(ResourceRequirements { requests: map(requests), limits: map(limits), ..Default::default() }),
+            ..Default::default()
+        }
+    }
+
+    fn pod(init: Vec<Container>, containers: Vec<Container>) -> Pod {
+        Pod {
+            spec: Some(PodSpec { init_containers: Some(init), containers, ..Default::default() }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn parses_quantities() {
+        assert_eq!(parse_quantity("1e3"), Some(1000.0));
+        assert_eq!(parse_quantity("500m"), Some(0.5));
+        assert_eq!(parse_quantity("128Mi"), Some(134217728.0));
+        assert_eq!(parse_quantity("2"), Some(2.0));
+        assert_eq!(parse_quantity("lots"), None);
+    }
+
+    #[test]
+    fn effective_sums_sidecars_and_takes_the_largest_init() {
+        let p = pod(
+            vec![
+                container("migrate", &[("cpu", "2")], &[], false),
+                container("proxy", &[("cpu", "250m")], &[], true),
+            ],
+            vec![container("app", &[("cpu", "500m")], &[], false)],
+        );
+        let (requests, _) = effective(&p);
+        // The init container alone (2) outweighs app + sidecar (0.75)
+        assert_eq!(requests[CPU], Some(2.0));
+
+        let p = pod(
+            vec![
+                container("migrate", &[("cpu", "100m")], &[], false),
+                container("proxy", &[("cpu", "250m")], &[], true),
+            ],
+            vec![container("app", &[("cpu", "500m")], &[], false)],
+        );
+        let (requests, limits) = effective(&p);
+        assert_eq!(requests[CPU], Some(0.75));
+        // No running container has a CPU limit
+        assert_eq!(limits[CPU], None);
+    }
+
+    #[test]
+    fn qos_prefers_the_status() {
+        let mut p = pod(vec![], vec![container("app", &[], &[], false)]);
+        assert_eq!(qos_class(&p), "BestEffort");
+        p.status = Some(PodStatus { qos_class: Some("Burstable".to_string()), ..Default::default() });
+        assert_eq!(qos_class(&p), "Burstable");
+    }
+
+    #[test]
+    fn qos_is_derived_from_the_spec() {
+        let guaranteed = container("app", &[], &[("cpu", "1"), ("memory", "1Gi")], false);
+        assert_eq!(qos_class(&pod(vec![], vec![guaranteed.clone()])), "Guaranteed");
+
+        let burstable = container("app", &[("cpu", "500m"), ("memory", "1Gi")], &[("cpu", "1"), ("memory", "1Gi")], false);
+        assert_eq!(qos_class(&pod(vec![], vec![burstable])), "Burstable");
+
+        // One unbounded container is enough to lose Guaranteed
+        let unbounded = container("sidecar", &[], &[], true);
+        assert_eq!(qos_class(&pod(vec![unbounded], vec![guaranteed])), "Burstable");
+    }
+
+    #[test]
+    fn finished_init_containers_are_not_warned_about() {
+        let p = pod(
+            vec![container("migrate", &[], &[], false), container("proxy", &[], &[], true)],
+            vec![container("app", &[], &[("memory", "1Gi")], false)],
+        );
+        let warnings = warnings(&p);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].starts_with("proxy: no memory limit"));
+    }
+}
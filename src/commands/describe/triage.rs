@@ -0,0 +1,233 @@
+use colored::*;
+use super::events::EventRecord;
+use k8s_openapi::api::core::v1::{ContainerStatus, Pod};
+
+/// How urgent a finding is; findings are listed most severe first.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Critical,
+    Warning,
+}
+
+/// One diagnosed problem: what is wrong, why, and what to do next.
+pub struct Finding {
+    pub severity: Severity,
+    pub title: String,
+    pub explanation: String,
+    pub next_step: String,
+}
+
+// A rule looks at the pod and its events and reports zero or more findings
+type Rule = fn(&Pod, &[EventRecord]) -> Vec<Finding>;
+
+// In tie-break order: among equally severe findings, earlier rules come first
+const RULES: &[Rule] = &[
+    unschedulable,
+    image_pull,
+    config_error,
+    oom_killed,
+    crash_loop,
+    evicted,
+    failed_probes,
+];
+
+// --- ENGINE ---
+/// Runs every rule and ranks the findings, most severe first.
+pub fn diagnose(pod: &Pod, events: &[EventRecord]) -> Vec<Finding> {
+    let mut findings: Vec<Finding> = RULES.iter().flat_map(|rule| rule(pod, events)).collect();
+    // Stable, so rule order breaks ties
+    findings.sort_by_key(|f| f.severity);
+    findings
+}
+
+pub fn print(findings: &[Finding]) {
+    if findings.is_empty() {
+        println!("   {} No known problems found", "✅".green());
+        return;
+    }
+    for (i, f) in findings.iter().enumerate() {
+        let (icon, title) = match f.severity {
+            Severity::Critical => ("🔴", f.title.red().bold()),
+            Severity::Warning => ("🟡", f.title.yellow().bold()),
+        };
+        println!(" {}. {} {}", i + 1, icon, title);
+        println!("      {} {}", "why: ".dimmed(), f.explanation);
+        println!("      {} {}", "next:".cyan(), f.next_step);
+    }
+}
+
+fn statuses(pod: &Pod) -> impl Iterator<Item = &ContainerStatus> {
+    let status = pod.status.as_ref();
+    let init = status.and_then(|s| s.init_container_statuses.as_deref()).unwrap_or_default();
+    let app = status.and_then(|s| s.container_statuses.as_deref()).unwrap_or_default();
+    init.iter().chain(app)
+}
+
+// Containers currently waiting for one of `reasons`, with the kubelet's message
+fn waiting<'a>(pod: &'a Pod, reasons: &'a [&str]) -> impl Iterator<Item = (&'a ContainerStatus, &'a str, String)> {
+    statuses(pod).filter_map(move |cs| {
+        let waiting = cs.state.as_ref()?.waiting.as_ref()?;
+        let reason = waiting.reason.as_deref().filter(|r| reasons.contains(r))?;
+        Some((cs, reason, waiting.message.clone().unwrap_or_default()))
+    })
+}
+
+fn pod_name(pod: &Pod) -> &str {
+    pod.metadata.name.as_deref().unwrap_or_default()
+}
+
+// --- RULES ---
+fn crash_loop(pod: &Pod, _: &[EventRecord]) -> Vec<Finding> {
+    waiting(pod, &["CrashLoopBackOff"])
+        .map(|(cs, _, _)| {
+            let last = cs.last_state.as_ref().and_then(|s| s.terminated.as_ref());
+            let exit = match last {
+                Some(t) => format!(
+                    "It last exited with code {} ({})",
+                    t.exit_code,
+                    t.reason.as_deref().unwrap_or("no reason given")
+                ),
+                None => "It keeps exiting".to_string(),
+            };
+            Finding {
+                severity: Severity::Critical,
+                title: format!("{} is crash-looping ({} restarts)", cs.name, cs.restart_count),
+                explanation: format!("{}, and the kubelet waits longer before each restart.", exit),
+                next_step: format!(
+                    "see why it exited with `klog log {} -n {} --previous -c` and pick {}",
+                    pod_name(pod),
+                    pod.metadata.namespace.as_deref().unwrap_or_default(),
+                    cs.name
+                ),
+            }
+        })
+        .collect()
+}
+
+fn oom_killed(pod: &Pod, _: &[EventRecord]) -> Vec<Finding> {
+    statuses(pod)
+        .filter(|cs| {
+            let current = cs.state.as_ref().and_then(|s| s.terminated.as_ref());
+            let last = cs.last_state.as_ref().and_then(|s| s.terminated.as_ref());
+            current.or(last).and_then(|t| t.reason.as_deref()) == Some("OOMKilled")
+        })
+        .map(|cs| {
+            let limit = pod
+                .spec
+                .as_ref()
+                .into_iter()
+                .flat_map(|s| s.init_containers.iter().flatten().chain(&s.containers))
+                .find(|c| c.name == cs.name)
+                .and_then(|c| c.resources.as_ref()?.limits.as_ref()?.get("memory"))
+                .map(|q| format!("its memory limit of {}", q.0))
+                .unwrap_or_else(|| "the memory available on the node".to_string());
+            Finding {
+                severity: Severity::Critical,
+                title: format!("{} was killed for running out of memory (OOMKilled)", cs.name),
+                explanation: format!("The container used more than {} and the kernel killed it.", limit),
+                next_step: "raise the memory limit, or look for a leak or an oversized cache/heap setting".to_string(),
+            }
+        })
+        .collect()
+}
+
+fn image_pull(pod: &Pod, _: &[EventRecord]) -> Vec<Finding> {
+    waiting(pod, &["ImagePullBackOff", "ErrImagePull", "InvalidImageName"])
+        .map(|(cs, reason, message)| Finding {
+            severity: Severity::Critical,
+            title: format!("{} cannot pull its image ({})", cs.name, reason),
+            explanation: format!("The node failed to fetch {}: {}", cs.image, or_unknown(&message)),
+            next_step: "check the image name and tag exist, and that imagePullSecrets grant access to the registry"
+                .to_string(),
+        })
+        .collect()
+}
+
+fn unschedulable(pod: &Pod, events: &[EventRecord]) -> Vec<Finding> {
+    let condition = pod
+        .status
+        .as_ref()
+        .and_then(|s| s.conditions.as_ref())
+        .into_iter()
+        .flatten()
+        .find(|c| c.type_ == "PodScheduled" && c.status == "False" && c.reason.as_deref() == Some("Unschedulable"));
+    let Some(condition) = condition else {
+        return vec![];
+    };
+    // The scheduler's own words, e.g. "0/3 nodes are available: 3 Insufficient cpu."
+    let reason = condition
+        .message
+        .clone()
+        .or_else(|| latest(events, |e| e.reason == "FailedScheduling").map(|e| e.message.clone()))
+        .unwrap_or_default();
+    vec![Finding {
+        severity: Severity::Critical,
+        title: "The pod cannot be scheduled onto any node".to_string(),
+        explanation: format!("The scheduler says: {}", or_unknown(&reason)),
+        next_step: "lower the pod's requests or relax its node selector, affinity and tolerations, or add capacity"
+            .to_string(),
+    }]
+}
+
+fn failed_probes(_: &Pod, events: &[EventRecord]) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    for (probe, severity, effect) in [
+        ("Liveness", Severity::Critical, "The kubelet restarts the container each time it keeps failing"),
+        ("Readiness", Severity::Warning, "The pod is taken out of its Services' endpoints while it fails"),
+        ("Startup", Severity::Warning, "The container is restarted if it doesn't start in time"),
+    ] {
+        let prefix = format!("{} probe failed", probe);
+        let failing = |e: &EventRecord| e.reason == "Unhealthy" && e.message.starts_with(&prefix);
+        let count: i32 = events.iter().filter(|e| failing(e)).map(|e| e.count).sum();
+        let Some(last) = latest(events, failing) else {
+            continue;
+        };
+        findings.push(Finding {
+            severity,
+            title: format!("{} probe failing ({}x)", probe, count),
+            explanation: format!("{}; last failure: {}", effect, last.message),
+            next_step: "check the probe's path, port and timeouts against what the app serves, and its startup time"
+                .to_string(),
+        });
+    }
+    findings
+}
+
+fn config_error(pod: &Pod, _: &[EventRecord]) -> Vec<Finding> {
+    waiting(pod, &["CreateContainerConfigError"])
+        .map(|(cs, _, message)| Finding {
+            severity: Severity::Critical,
+            title: format!("{} cannot start: its configuration is incomplete", cs.name),
+            explanation: format!(
+                "A ConfigMap, Secret or key it references is missing: {}",
+                or_unknown(&message)
+            ),
+            next_step: "create the missing ConfigMap/Secret or key in the pod's namespace, or mark the reference optional"
+                .to_string(),
+        })
+        .collect()
+}
+
+fn evicted(pod: &Pod, _: &[EventRecord]) -> Vec<Finding> {
+    let status = pod.status.as_ref();
+    if status.and_then(|s| s.reason.as_deref()) != Some("Evicted") {
+        return vec![];
+    }
+    let message = status.and_then(|s| s.message.clone()).unwrap_or_default();
+    vec![Finding {
+        severity: Severity::Warning,
+        title: "The pod was evicted from its node".to_string(),
+        explanation: format!("The kubelet removed it to free resources: {}", or_unknown(&message)),
+        next_step: "set requests that match real usage so it isn't first in line, and check the node's pressure".to_string(),
+    }]
+}
+
+// --- HELPERS ---
+// The most recent event matching `pred`
+fn latest(events: &[EventRecord], pred: impl Fn(&EventRecord) -> bool) -> Option<&EventRecord> {
+    events.iter().filter(|e| pred(e)).max_by_key(|e| e.last)
+}
+
+fn or_unknown(message: &str) -> &str {
+    if message.is_empty() { "no details given" } else { message }
+}
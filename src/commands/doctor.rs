@@ -0,0 +1,171 @@
+use crate::error::KlogError;
+use crate::session::ConnectionArgs;
+use colored::*;
+use k8s_openapi::api::authentication::v1::SelfSubjectReview;
+use kube::{Api, Client, Config, api::PostParams};
+use rustls::pki_types::{CertificateDer, ServerName};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+// Used for each network stage when --connect-timeout isn't given
+const STAGE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Walks the connection one layer at a time (config, DNS, TCP, proxy, TLS, auth)
+/// and stops at the first layer that fails, so it's clear where to look.
+pub async fn run(connection: &ConnectionArgs) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    println!("\n{}", "--- CONNECTION DOCTOR ---".bold().bright_white());
+
+    // 1. Kubeconfig
+    let config = report("Config", async {
+        let (config, context) = connection.config().await?;
+        let detail = format!("context {} → {}", context, config.cluster_url);
+        Ok((config, detail))
+    })
+    .await?;
+
+    let host = config.cluster_url.host().unwrap_or_default().trim_matches(['[', ']']).to_string();
+    let port = config.cluster_url.port_u16().unwrap_or(443);
+    let timeout = config.connect_timeout.unwrap_or(STAGE_TIMEOUT);
+    // Either from --proxy / $HTTPS_PROXY or the kubeconfig's proxy-url
+    let (dial_host, dial_port) = match &config.proxy_url {
+        Some(uri) => (uri.host().unwrap_or_default().to_string(), uri.port_u16().unwrap_or(80)),
+        None => (host.clone(), port),
+    };
+
+    // 2. DNS (of the proxy when there is one; it resolves the API server itself)
+    let addrs: Vec<SocketAddr> = report("DNS", async {
+        let addrs: Vec<SocketAddr> = within(timeout, "DNS lookup", tokio::net::lookup_host((dial_host.as_str(), dial_port)))
+            .await?
+            .map_err(|e| KlogError::Unreachable(format!("cannot resolve {}: {}", dial_host, e)))?
+            .collect();
+        let ips: Vec<String> = addrs.iter().map(|a| a.ip().to_string()).collect();
+        Ok((addrs, format!("{} → {}", dial_host, ips.join(", "))))
+    })
+    .await?;
+
+    // 3. TCP
+    let mut stream = report("TCP", async {
+        let stream = within(timeout, "TCP connect", TcpStream::connect(&addrs[..]))
+            .await?
+            .map_err(|e| KlogError::Unreachable(format!("cannot connect to {}:{}: {}", dial_host, dial_port, e)))?;
+        let peer = stream.peer_addr().map(|a| a.to_string()).unwrap_or_default();
+        Ok((stream, format!("connected to {}", peer)))
+    })
+    .await?;
+
+    if config.proxy_url.is_some() {
+        report("Proxy", async {
+            within(timeout, "proxy CONNECT", proxy_connect(&mut stream, &host, port)).await??;
+            Ok(((), format!("CONNECT {}:{} accepted", host, port)))
+        })
+        .await?;
+    }
+
+    // 4. TLS
+    if config.cluster_url.scheme_str() == Some("https") {
+        if config.accept_invalid_certs {
+            println!("  {} {:<7} {}", "⚠️ ".yellow(), "TLS", "verification disabled by the kubeconfig (insecure-skip-tls-verify)".yellow());
+        } else {
+            let name = config.tls_server_name.clone().unwrap_or_else(|| host.clone());
+            report("TLS", async {
+                let version = within(timeout, "TLS handshake", tls_handshake(&config, &name, stream)).await??;
+                Ok(((), format!("{} handshake ok, certificate valid for {}", version, name)))
+            })
+            .await?;
+        }
+    }
+
+    // 5. Authentication
+    report("Auth", async {
+        let client = Client::try_from(config)?;
+        let api: Api<SelfSubjectReview> = Api::all(client);
+        let review = api.create(&PostParams::default(), &SelfSubjectReview::default()).await?;
+        let user = review
+            .status
+            .and_then(|s| s.user_info)
+            .and_then(|u| u.username)
+            .unwrap_or_else(|| "unknown user".to_string());
+        Ok(((), format!("authenticated as {}", user)))
+    })
+    .await?;
+
+    println!("\n{}", "All stages passed.".green().bold());
+    Ok(())
+}
+
+// Prints one stage's outcome and passes its value (or error) on
+async fn report<T>(
+    stage: &str,
+    check: impl std::future::Future<Output = Result<(T, String), KlogError>>,
+) -> Result<T, KlogError> {
+    let started = Instant::now();
+    let result = check.await;
+    let took = format!("({} ms)", started.elapsed().as_millis()).dimmed();
+    match result {
+        Ok((value, detail)) => {
+            println!("  {} {:<7} {} {}", "✅".green(), stage, detail, took);
+            Ok(value)
+        }
+        Err(e) => {
+            println!("  {} {:<7} {} {}", "❌".red(), stage, e.to_string().red(), took);
+            Err(e)
+        }
+    }
+}
+
+async fn within<T>(limit: Duration, what: &str, fut: impl std::future::Future<Output = T>) -> Result<T, KlogError> {
+    tokio::time::timeout(limit, fut)
+        .await
+        .map_err(|_| KlogError::Timeout(format!("{} took longer than {}s", what, limit.as_secs())))
+}
+
+async fn proxy_connect(stream: &mut TcpStream, host: &str, port: u16) -> Result<(), KlogError> {
+    let request = format!("CONNECT {host}:{port} HTTP/1.1\r\nHost: {host}:{port}\r\n\r\n");
+    let io = |e: std::io::Error| KlogError::Unreachable(format!("proxy connection failed: {}", e));
+    stream.write_all(request.as_bytes()).await.map_err(io)?;
+
+    // Read just the response head; the tunnel starts right after it
+    let mut head = Vec::new();
+    let mut byte = [0u8; 1];
+    while !head.ends_with(b"\r\n\r\n") && head.len() < 8192 {
+        if stream.read(&mut byte).await.map_err(io)? == 0 {
+            break;
+        }
+        head.push(byte[0]);
+    }
+    let head = String::from_utf8_lossy(&head);
+    let status = head.lines().next().unwrap_or_default().to_string();
+    if status.split_whitespace().nth(1) == Some("200") {
+        Ok(())
+    } else {
+        Err(KlogError::Unreachable(format!("proxy refused the tunnel: {}", status)))
+    }
+}
+
+async fn tls_handshake(config: &Config, name: &str, stream: TcpStream) -> Result<String, KlogError> {
+    let mut roots = rustls::RootCertStore::empty();
+    match &config.root_cert {
+        Some(certs) => {
+            for der in certs {
+                let _ = roots.add(CertificateDer::from(der.clone()));
+            }
+        }
+        None => {
+            roots.add_parsable_certificates(rustls_native_certs::load_native_certs().certs);
+        }
+    }
+    let tls = rustls::ClientConfig::builder().with_root_certificates(roots).with_no_client_auth();
+    let server_name = ServerName::try_from(name.to_string())
+        .map_err(|e| KlogError::InvalidArgument(format!("invalid TLS server name '{}': {}", name, e)))?;
+
+    let connector = tokio_rustls::TlsConnector::from(Arc::new(tls));
+    let stream = connector
+        .connect(server_name, stream)
+        .await
+        .map_err(|e| KlogError::Tls(format!("handshake with {} failed: {}", name, e)))?;
+    let version = stream.get_ref().1.protocol_version().map(|v| format!("{:?}", v)).unwrap_or_default();
+    Ok(version.replace("_", "."))
+}
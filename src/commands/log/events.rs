@@ -0,0 +1,230 @@
+use super::render;
+use super::worker;
+use crate::error::KlogError;
+use crate::models::{LogMessage, PodEvent, PodOption, StreamError, StreamEvent};
+use futures::StreamExt;
+use jiff::Timestamp;
+use k8s_openapi::api::core::v1::Event;
+use kube::{
+    Api, Client,
+    runtime::{WatchStreamExt, watcher},
+};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+};
+use std::collections::VecDeque;
+use tokio::sync::mpsc::Sender;
+use tokio::task::JoinSet;
+
+const LOG_SCROLLBACK: usize = 2000;
+const EVENT_ROWS: usize = 200;
+// Below this width the two regions are stacked instead of side by side
+const SIDE_BY_SIDE_MIN_COLS: u16 = 120;
+
+// --- EVENT WATCHERS ---
+// One watcher per pod, using the same `involvedObject` selector as describe.
+// The watcher's initial list (and every relist) returns old events too; only
+// those last seen since `started` (when streaming began) are shown.
+pub fn watch_events(
+    client: Client,
+    pods: &[PodOption],
+    started: Timestamp,
+    tx: Sender<StreamEvent>,
+    tasks: &mut JoinSet<()>,
+) {
+    for pod in pods {
+        let api: Api<Event> = Api::namespaced(client.clone(), &pod.namespace);
+        let config = watcher::Config::default().fields(&format!("involvedObject.kind=Pod,involvedObject.name={}", pod.name));
+        let (tx, pod) = (tx.clone(), pod.clone());
+        tasks.spawn(async move {
+            let mut stream = watcher(api, config).default_backoff().applied_objects().boxed();
+            // The backoff keeps retrying; a failure is reported once until the watch recovers
+            let mut reported = false;
+            while let Some(item) = stream.next().await {
+                let e = match item {
+                    Ok(e) => e,
+                    Err(err) if !reported => {
+                        reported = true;
+                        let error = StreamError {
+                            cluster: pod.cluster.clone(),
+                            namespace: pod.namespace.clone(),
+                            pod_name: pod.name.clone(),
+                            container_name: None,
+                            error: watch_error(err),
+                            fatal: false,
+                        };
+                        if tx.send(StreamEvent::Error(error)).await.is_err() {
+                            break;
+                        }
+                        continue;
+                    }
+                    Err(_) => continue,
+                };
+                reported = false;
+                if last_seen(&e).is_some_and(|t| t < started) {
+                    continue;
+                }
+                if tx.send(StreamEvent::Kube(to_pod_event(e, pod.cluster.clone(), pod.namespace.clone()))).await.is_err() {
+                    break;
+                }
+            }
+        });
+    }
+}
+
+// "forbidden: cannot watch events in namespace ns" rather than an empty panel
+fn watch_error(err: watcher::Error) -> KlogError {
+    match worker::forbidden(&err) {
+        Some(denied) => denied,
+        None => KlogError::Other(format!("events unavailable: {}", err)),
+    }
+}
+
+fn last_seen(e: &Event) -> Option<Timestamp> {
+    e.last_timestamp
+        .as_ref()
+        .map(|t| t.0)
+        .or_else(|| e.series.as_ref().and_then(|s| s.last_observed_time.as_ref()).map(|t| t.0))
+        .or_else(|| e.event_time.as_ref().map(|t| t.0))
+}
+
+fn to_pod_event(e: Event, cluster: Option<String>, namespace: String) -> PodEvent {
+    let time = last_seen(&e).map(|t| t.strftime("%H:%M:%S").to_string()).unwrap_or_default();
+    PodEvent {
+        uid: e.metadata.uid.unwrap_or_default(),
+        cluster,
+        namespace,
+        pod_name: e.involved_object.name.unwrap_or_default(),
+        warning: e.type_.as_deref() == Some("Warning"),
+        reason: e.reason.unwrap_or_default(),
+        message: e.message.unwrap_or_default(),
+        count: e.count.unwrap_or(1),
+        time,
+    }
+}
+
+/// Merged log stream on one side, live pod events on the other.
+pub struct EventsView {
+    logs: VecDeque<Line<'static>>,
+    events: VecDeque<PodEvent>,
+}
+
+impl EventsView {
+    pub fn new() -> Self {
+        Self { logs: VecDeque::new(), events: VecDeque::new() }
+    }
+
+    pub fn push_log(&mut self, log: &LogMessage) {
+        let color = match render::color_slot(&log.pod_name) {
+            0 => Color::Cyan,
+            1 => Color::Green,
+            2 => Color::Magenta,
+            _ => Color::Yellow,
+        };
+        self.push_line(Line::from(vec![
+            Span::styled(
+                format!("[{}] ", log.stream()),
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(log.message.clone()),
+        ]));
+    }
+
+    pub fn push_banner(&mut self, text: String, style: Style) {
+        self.push_line(Line::from(Span::styled(text, style)));
+    }
+
+    fn push_line(&mut self, line: Line<'static>) {
+        if self.logs.len() >= LOG_SCROLLBACK {
+            self.logs.pop_front();
+        }
+        self.logs.push_back(line);
+    }
+
+    /// Records an event; repeats of the same event (count bumps) replace the old row.
+    /// Returns true if this is a warning worth flagging in the log region.
+    pub fn push_event(&mut self, event: PodEvent) -> bool {
+        let is_new = match self.events.iter().position(|e| e.uid == event.uid) {
+            Some(i) => {
+                let old = self.events.remove(i);
+                old.is_some_and(|o| o.count != event.count)
+            }
+            None => true,
+        };
+        let warning = event.warning && is_new;
+        if self.events.len() >= EVENT_ROWS {
+            self.events.pop_front();
+        }
+        self.events.push_back(event);
+        warning
+    }
+
+    pub fn draw(&self, frame: &mut Frame, status: &str) {
+        let [body, footer] = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(1)])
+            .areas(frame.size());
+
+        let (direction, split) = if body.width >= SIDE_BY_SIDE_MIN_COLS {
+            (Direction::Horizontal, [Constraint::Percentage(60), Constraint::Percentage(40)])
+        } else {
+            (Direction::Vertical, [Constraint::Percentage(65), Constraint::Percentage(35)])
+        };
+        let [logs_area, events_area] = Layout::default().direction(direction).constraints(split).areas(body);
+
+        let height = logs_area.height.saturating_sub(2) as usize;
+        let logs: Vec<Line> = self.logs.iter().skip(self.logs.len().saturating_sub(height)).cloned().collect();
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::DarkGray))
+            .title(" Logs ");
+        frame.render_widget(Paragraph::new(logs).block(block), logs_area);
+
+        let warnings = self.events.iter().filter(|e| e.warning).count();
+        let height = events_area.height.saturating_sub(2) as usize;
+        let rows: Vec<Line> = self
+            .events
+            .iter()
+            .skip(self.events.len().saturating_sub(height))
+            .map(event_line)
+            .collect();
+        let border = if warnings > 0 { Color::Red } else { Color::DarkGray };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(border))
+            .title(format!(" Events ({} warnings) ", warnings));
+        frame.render_widget(Paragraph::new(rows).block(block), events_area);
+
+        let keys = Line::from(vec![
+            Span::styled(format!(" {} ", status), Style::default().fg(Color::Green)),
+            Span::styled("| ", Style::default().fg(Color::DarkGray)),
+            Span::styled("e", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            Span::styled(" Merged view  ", Style::default().fg(Color::DarkGray)),
+            Span::styled("v", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            Span::styled(" Split view  ", Style::default().fg(Color::DarkGray)),
+            Span::styled("q", Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)),
+            Span::styled(" Quit", Style::default().fg(Color::DarkGray)),
+        ]);
+        frame.render_widget(Paragraph::new(keys), footer);
+    }
+}
+
+fn event_line(e: &PodEvent) -> Line<'static> {
+    let (kind, style) = if e.warning {
+        ("Warning", Style::default().fg(Color::Red).add_modifier(Modifier::BOLD))
+    } else {
+        ("Normal", Style::default().fg(Color::Green))
+    };
+    let count = if e.count > 1 { format!(" x{}", e.count) } else { String::new() };
+    Line::from(vec![
+        Span::styled(format!("{} ", e.time), Style::default().fg(Color::DarkGray)),
+        Span::styled(format!("{:<7} ", kind), style),
+        Span::styled(format!("{} ", e.pod()), Style::default().fg(Color::Cyan)),
+        Span::styled(format!("{}{} ", e.reason, count), style),
+        Span::raw(e.message.clone()),
+    ])
+}
@@ -0,0 +1,145 @@
+use colored::*;
+use crossterm::{cursor, execute, queue, terminal};
+use std::io::{stdout, Write};
+
+const SPARKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+// The last minute is squeezed into this many cells (3s per cell)
+const SPARK_WIDTH: usize = 20;
+
+/// Everything the status bar shows, snapshotted by the stream loop.
+pub struct Status<'a> {
+    pub active: usize,
+    pub ended: &'a [String],
+    /// Streams that stopped on an error
+    pub failed: usize,
+    /// Non-fatal stream errors so far
+    pub errors: usize,
+    pub rate: f64,
+    pub noisiest: Option<(&'a str, f64)>,
+    pub hidden: u64,
+    pub history_len: usize,
+    pub history_cap: usize,
+    pub paused: Option<usize>,
+    pub volume: &'a [u32],
+}
+
+// --- SCROLL REGION ---
+// Logs scroll in rows 0..rows-1 while the last row stays pinned for the
+// status bar, so it can be redrawn on a timer instead of after every line.
+pub fn reserve_row() {
+    let (_, rows) = terminal::size().unwrap_or((80, 24));
+    // Push the current screen up one line so the first log line doesn't overwrite it
+    let _ = write!(stdout(), "\r\n");
+    set_region(rows);
+}
+
+/// (Re)applies the scroll region for a terminal `rows` high, e.g. after a resize.
+pub fn set_region(rows: u16) {
+    let mut out = stdout();
+    let _ = write!(out, "\x1b[1;{}r", rows.saturating_sub(1).max(1));
+    let _ = execute!(out, cursor::MoveTo(0, rows.saturating_sub(2)));
+}
+
+pub fn release_row() {
+    let _ = write!(stdout(), "\x1b[r");
+    let _ = stdout().flush();
+}
+
+pub fn draw(status: &Status) {
+    let (cols, rows) = terminal::size().unwrap_or((80, 24));
+
+    // Each segment is (plain text for width math, styled text to print).
+    // Segments that don't fit are dropped from the right, keybindings always stay.
+    let mut segments: Vec<(String, String)> = Vec::new();
+
+    let total = status.active + status.ended.len() + status.failed;
+    let streams = format!("● {}/{} streams", status.active, total);
+    let styled = if status.failed > 0 {
+        streams.red()
+    } else if status.ended.is_empty() {
+        streams.green()
+    } else {
+        streams.yellow()
+    };
+    segments.push((streams.clone(), styled.to_string()));
+
+    if status.failed > 0 || status.errors > 0 {
+        let errors = format!("✖ {} failed {} errors", status.failed, status.errors);
+        segments.push((errors.clone(), errors.red().bold().to_string()));
+    }
+
+    if let Some(first) = status.ended.first() {
+        let more = status.ended.len() - 1;
+        let ended = if more > 0 { format!("ended {} +{}", first, more) } else { format!("ended {}", first) };
+        segments.push((ended.clone(), ended.yellow().to_string()));
+    }
+
+    if let Some(queued) = status.paused {
+        let paused = format!("⏸ PAUSED ({} queued)", queued);
+        segments.push((paused.clone(), paused.on_yellow().black().to_string()));
+    }
+
+    let rate = format!("{:.1} l/s", status.rate);
+    segments.push((rate.clone(), rate.bold().to_string()));
+
+    let spark = sparkline(status.volume);
+    segments.push((spark.clone(), spark.cyan().to_string()));
+
+    if let Some((stream, rate)) = status.noisiest {
+        let top = format!("top {} {:.1} l/s", stream, rate);
+        segments.push((top.clone(), top.magenta().to_string()));
+    }
+
+    if status.hidden > 0 {
+        let hidden = format!("{} hidden", status.hidden);
+        segments.push((hidden.clone(), hidden.dimmed().to_string()));
+    }
+
+    let history = format!("hist {}/{}", status.history_len, status.history_cap);
+    segments.push((history.clone(), history.dimmed().to_string()));
+
+    let keys_plain = "s Search p Pause v Split e Events q Quit";
+    let keys = format!(
+        "{} {} {} {} {} {} {} {} {} {}",
+        "s".bold().cyan(), "Search".dimmed(),
+        "p".bold().cyan(), "Pause".dimmed(),
+        "v".bold().cyan(), "Split".dimmed(),
+        "e".bold().cyan(), "Events".dimmed(),
+        "q".bold().red(), "Quit".dimmed()
+    );
+
+    let budget = (cols as usize).saturating_sub(keys_plain.chars().count() + 4);
+    let mut used = 0;
+    let mut line = String::from(" ");
+    for (plain, styled) in segments {
+        let width = plain.chars().count() + 3;
+        if used + width > budget {
+            break;
+        }
+        used += width;
+        line.push_str(&styled);
+        line.push_str(&" | ".dimmed().to_string());
+    }
+    line.push_str(&keys);
+
+    let mut out = stdout();
+    let _ = queue!(
+        out,
+        cursor::SavePosition,
+        cursor::MoveTo(0, rows.saturating_sub(1)),
+        terminal::Clear(terminal::ClearType::CurrentLine)
+    );
+    let _ = write!(out, "{}", line);
+    let _ = queue!(out, cursor::RestorePosition);
+    let _ = out.flush();
+}
+
+fn sparkline(volume: &[u32]) -> String {
+    let per_cell = volume.len().div_ceil(SPARK_WIDTH).max(1);
+    let cells: Vec<u32> = volume.chunks(per_cell).map(|c| c.iter().sum()).collect();
+    let peak = cells.iter().copied().max().unwrap_or(0).max(1);
+    cells
+        .iter()
+        .map(|&v| SPARKS[(v as usize * (SPARKS.len() - 1)) / peak as usize])
+        .collect()
+}
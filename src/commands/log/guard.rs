@@ -0,0 +1,90 @@
+use super::footer;
+use crossterm::{cursor, execute, queue, terminal};
+use std::io::{stdout, Write};
+use std::sync::Once;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+// Set while the stream owns the terminal, so the panic hook knows to clean up
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+// Set while the split view owns the alternate screen
+static ALTERNATE: AtomicBool = AtomicBool::new(false);
+static PANIC_HOOK: Once = Once::new();
+
+/// Raw mode plus the pinned footer row. Dropping the guard (normal exit,
+/// early `?` return or unwinding panic) always hands a sane terminal back.
+pub struct TerminalGuard;
+
+impl TerminalGuard {
+    pub fn enter() -> std::io::Result<Self> {
+        PANIC_HOOK.call_once(|| {
+            let previous = std::panic::take_hook();
+            std::panic::set_hook(Box::new(move |info| {
+                // Restore first, otherwise the panic message is mangled by raw mode
+                if ACTIVE.swap(false, Ordering::SeqCst) {
+                    restore();
+                }
+                previous(info);
+            }));
+        });
+
+        terminal::enable_raw_mode()?;
+        ACTIVE.store(true, Ordering::SeqCst);
+        footer::reserve_row();
+        Ok(Self)
+    }
+
+    /// Leaves raw mode for an interactive prompt, keeping the footer row.
+    pub fn suspend(&self) -> std::io::Result<()> {
+        terminal::disable_raw_mode()
+    }
+
+    pub fn resume(&self) -> std::io::Result<()> {
+        terminal::enable_raw_mode()
+    }
+
+    /// Hands the whole screen to the split view; the merged scrollback is kept intact.
+    pub fn enter_alternate(&self) -> std::io::Result<()> {
+        footer::release_row();
+        execute!(stdout(), terminal::EnterAlternateScreen, cursor::Hide)?;
+        ALTERNATE.store(true, Ordering::SeqCst);
+        Ok(())
+    }
+
+    pub fn leave_alternate(&self) -> std::io::Result<()> {
+        if ALTERNATE.swap(false, Ordering::SeqCst) {
+            execute!(stdout(), terminal::LeaveAlternateScreen, cursor::Show)?;
+        }
+        footer::reserve_row();
+        Ok(())
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        if ACTIVE.swap(false, Ordering::SeqCst) {
+            restore();
+        }
+    }
+}
+
+fn restore() {
+    if ALTERNATE.swap(false, Ordering::SeqCst) {
+        let _ = execute!(stdout(), terminal::LeaveAlternateScreen);
+    }
+    footer::release_row();
+    let _ = terminal::disable_raw_mode();
+    let (_, rows) = terminal::size().unwrap_or((80, 24));
+
+    // Jump to the bottom line and clear it entirely
+    let mut out = stdout();
+    let _ = queue!(
+        out,
+        cursor::Show,
+        cursor::MoveTo(0, rows.saturating_sub(1)),
+        terminal::Clear(terminal::ClearType::CurrentLine)
+    );
+
+    // Ensure the cursor is moved to a new line so the prompt is clean
+    let _ = write!(out, "\r\n");
+    let _ = out.flush();
+}
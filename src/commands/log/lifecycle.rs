@@ -0,0 +1,104 @@
+use crate::models::{Marker, PodOption, StreamEvent};
+use futures::StreamExt;
+use k8s_openapi::api::core::v1::{ContainerStatus, Pod};
+use kube::{
+    Api, Client,
+    runtime::{WatchStreamExt, watcher},
+};
+use std::collections::HashMap;
+use tokio::sync::mpsc::Sender;
+use tokio::task::JoinSet;
+
+// What we remember about a container between two pod updates
+#[derive(Clone, Copy, PartialEq)]
+struct Seen {
+    restarts: i32,
+    terminated: bool,
+}
+
+// --- POD WATCHERS ---
+// Turns status changes of the tailed pods into marker lines in the stream.
+pub fn watch_pods(client: Client, pods: &[PodOption], tx: Sender<StreamEvent>, tasks: &mut JoinSet<()>) {
+    for pod in pods {
+        let api: Api<Pod> = Api::namespaced(client.clone(), &pod.namespace);
+        let config = watcher::Config::default().fields(&format!("metadata.name={}", pod.name));
+        let (pod, tx) = (pod.clone(), tx.clone());
+        tasks.spawn(async move {
+            let mut seen: HashMap<String, Seen> = HashMap::new();
+            let mut terminating = false;
+            let mut stream = watcher(api, config).default_backoff().boxed();
+
+            while let Some(event) = stream.next().await {
+                let markers = match event {
+                    Ok(watcher::Event::Apply(p) | watcher::Event::InitApply(p)) => {
+                        let mut markers = diff_statuses(&pod, &p, &mut seen);
+                        if !terminating && p.metadata.deletion_timestamp.is_some() {
+                            terminating = true;
+                            markers.push(marker(&pod, None, "pod terminating".to_string()));
+                        }
+                        markers
+                    }
+                    Ok(watcher::Event::Delete(_)) => vec![marker(&pod, None, "pod deleted".to_string())],
+                    _ => continue,
+                };
+                for m in markers {
+                    if tx.send(StreamEvent::Marker(m)).await.is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+    }
+}
+
+fn diff_statuses(pod: &PodOption, p: &Pod, seen: &mut HashMap<String, Seen>) -> Vec<Marker> {
+    let statuses = p
+        .status
+        .as_ref()
+        .and_then(|s| s.container_statuses.clone())
+        .unwrap_or_default();
+
+    let mut markers = Vec::new();
+    for cs in statuses {
+        let now = Seen {
+            restarts: cs.restart_count,
+            terminated: cs.state.as_ref().is_some_and(|s| s.terminated.is_some()),
+        };
+        // The first sighting is just the baseline
+        let Some(before) = seen.insert(cs.name.clone(), now) else { continue };
+
+        if now.restarts > before.restarts {
+            markers.push(marker(pod, Some(&cs.name), format!("container {} restarted{}", cs.name, last_exit(&cs))));
+        } else if now.terminated && !before.terminated {
+            markers.push(marker(pod, Some(&cs.name), format!("container {} terminated{}", cs.name, current_exit(&cs))));
+        }
+    }
+    markers
+}
+
+// ", exit code 137 OOMKilled" from the previous instance of a restarted container
+fn last_exit(cs: &ContainerStatus) -> String {
+    cs.last_state
+        .as_ref()
+        .and_then(|s| s.terminated.as_ref())
+        .map(|t| format!(", exit code {} {}", t.exit_code, t.reason.clone().unwrap_or_default()))
+        .unwrap_or_default()
+}
+
+fn current_exit(cs: &ContainerStatus) -> String {
+    cs.state
+        .as_ref()
+        .and_then(|s| s.terminated.as_ref())
+        .map(|t| format!(", exit code {} {}", t.exit_code, t.reason.clone().unwrap_or_default()))
+        .unwrap_or_default()
+}
+
+fn marker(pod: &PodOption, container: Option<&str>, text: String) -> Marker {
+    Marker {
+        cluster: pod.cluster.clone(),
+        namespace: pod.namespace.clone(),
+        pod_name: pod.name.clone(),
+        container_name: container.map(str::to_string),
+        text: text.trim_end().to_string(),
+    }
+}
@@ -0,0 +1,823 @@
+use std::collections::{HashMap, VecDeque};
+use std::time::Instant;
+
+mod events;
+mod footer;
+mod guard;
+mod lifecycle;
+pub mod pipeline;
+pub mod render;
+mod split;
+pub mod stats;
+mod worker;
+
+use crate::models::{LogMessage, Marker, PodOption, StreamError, StreamEvent, stream_label};
+use events::EventsView;
+use pipeline::{HISTORY_CAP, Pipeline};
+use guard::TerminalGuard;
+use split::SplitView;
+use stats::{Alert, StreamStats, Thresholds};
+use worker::WorkerOptions;
+use crate::error::{KlogError, NotRetryable};
+use crate::rbac::{self, Permission};
+use crate::retry::RetryPolicy;
+use crate::session::{ConnectionArgs, Session};
+use crate::utils;
+use clap::{Args, ValueEnum};
+use colored::*;
+use futures::StreamExt;
+use inquire::{MultiSelect, Select};
+use k8s_openapi::api::core::v1::Pod;
+use kube::{Api, Client};
+use regex::Regex;
+use jiff::Timestamp;
+use crossterm::event::{Event, EventStream, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::{
+    Terminal,
+    backend::CrosstermBackend,
+    style::{Color, Modifier, Style},
+};
+use std::io::{stdout, BufWriter, Stdout, Write};
+use std::time::Duration;
+use tokio::task::JoinSet;
+
+#[derive(Args, Clone, Debug)]
+pub struct LogArgs {
+    /// If specified, fetch logs from this pod only
+    pub pod: Option<String>,
+    #[arg(short, long, num_args = 0..=1, default_missing_value = None)]
+    /// If specified, fetch logs from pods in this deployment only
+    pub deployment: Option<Option<String>>,
+    /// If specified, list pods from this namespace only. If pass -n only then list namespaces to choose from. IF not specified, use current context namespace.
+    #[arg(short, long, num_args = 0..=1, default_missing_value = None)]
+    pub namespace: Option<Option<String>>,
+    /// If specified, prompt to select containers within pods
+    #[arg(short, long, default_value_t = false)]
+    pub container_select: bool,
+    /// Filter logs by this string (regex supported)
+    #[arg(short, long)]
+    pub filter: Option<String>,
+    /// Exclude logs by this string (regex supported)
+    #[arg(short, long)]
+    pub exclude: Option<String>,
+    /// If specified, fetch previous logs
+    #[arg(short, long, default_value_t = false)]
+    pub previous: bool,
+    /// Number of lines from the end of the logs to show. * for all
+    #[arg(short, long, default_value = "50")]
+    pub tail: String,
+    /// When a container restarts, print this many lines of the terminated instance before following the new one
+    #[arg(
+        long,
+        value_name = "LINES",
+        num_args = 0..=1,
+        default_missing_value = "20",
+        value_parser = clap::value_parser!(u32).range(1..),
+        conflicts_with = "no_reconnect"
+    )]
+    pub restart_previous: Option<u32>,
+    /// Print the previous instance's logs (up to --tail lines), then follow the current one
+    #[arg(long, default_value_t = false, conflicts_with = "previous")]
+    pub previous_then_current: bool,
+    /// Seconds to wait for Pending pods / starting containers before giving up. 0 waits forever
+    #[arg(long, default_value_t = 300)]
+    pub wait_timeout: u64,
+    /// Don't reconnect when the API server drops a stream; end it instead
+    #[arg(long, default_value_t = false)]
+    pub no_reconnect: bool,
+    /// Lines matching this regex count as errors for spike detection
+    #[arg(long, default_value = r"(?i)\b(error|fatal|panic|exception)\b", value_parser = valid_regex)]
+    pub error_pattern: String,
+    /// Warn when a pod logs at least this many errors within --spike-window. 0 disables
+    #[arg(long, default_value_t = 10)]
+    pub spike_threshold: usize,
+    /// Sliding window (seconds) used for error spike detection
+    #[arg(long, default_value_t = 10)]
+    pub spike_window: u64,
+    /// Warn when a pod that was logging stays silent for this many seconds. 0 disables
+    #[arg(long, default_value_t = 30)]
+    pub silence: u64,
+    /// Tail the same selection in several kube contexts at once (comma separated); lines are prefixed with the context
+    #[arg(long, value_delimiter = ',', value_name = "CONTEXT")]
+    pub contexts: Vec<String>,
+    /// Start in the merged stream, one pane per pod, or logs next to pod events. Press v / e to switch while streaming
+    #[arg(long, value_enum, default_value_t = StreamLayout::Merged)]
+    pub layout: StreamLayout,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StreamLayout {
+    /// All pods interleaved in one scrolling stream
+    Merged,
+    /// Full-screen tiles, one per pod/container
+    Split,
+    /// Merged stream next to a live watch of the pods' Kubernetes events
+    Events,
+}
+
+/// User supplied regexes, compiled up front so a typo fails before any prompt or raw mode.
+pub struct Patterns {
+    pub filter: Option<Regex>,
+    pub exclude: Option<Regex>,
+    pub error: Regex,
+}
+
+impl Patterns {
+    fn compile(args: &LogArgs) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(Self {
+            filter: args.filter.as_deref().map(|f| compile_pattern("--filter", f)).transpose()?,
+            exclude: args.exclude.as_deref().map(|e| compile_pattern("--exclude", e)).transpose()?,
+            error: compile_pattern("--error-pattern", &args.error_pattern)?,
+        })
+    }
+}
+
+fn compile_pattern(flag: &str, pattern: &str) -> Result<Regex, Box<dyn std::error::Error + Send + Sync>> {
+    Regex::new(pattern).map_err(|e| {
+        KlogError::InvalidPattern { flag: flag.to_string(), pattern: pattern.to_string(), reason: e.to_string() }.into()
+    })
+}
+
+// clap value parser: rejects a bad regex while the flags are parsed
+fn valid_regex(pattern: &str) -> Result<String, String> {
+    Regex::new(pattern).map(|_| pattern.to_string()).map_err(|e| e.to_string())
+}
+
+// "*" means the whole log; anything else must be a line count
+fn parse_tail(tail: &str) -> Result<Option<i64>, Box<dyn std::error::Error + Send + Sync>> {
+    if tail == "*" {
+        return Ok(None);
+    }
+    match tail.parse::<i64>() {
+        Ok(n) if n >= 0 => Ok(Some(n)),
+        _ => Err(KlogError::InvalidArgument(format!("invalid --tail value '{}': expected a number of lines or *", tail)).into()),
+    }
+}
+
+// Checks up front that every selected namespace allows what the chosen path needs,
+// so a missing `pods/log get` doesn't surface as a wall of per-stream errors.
+// Waiting for containers and the lifecycle markers list and watch each pod by name.
+async fn preflight(session: &Session, namespaces: &[String], by_name: bool, pick_deployment: bool) -> Result<(), KlogError> {
+    let pb = utils::create_spinner("Checking permissions...");
+    let mut perms = Vec::new();
+    for ns in namespaces {
+        let ns = Some(ns.as_str());
+        if by_name {
+            perms.push(Permission::new("get", "pods", ns));
+        }
+        perms.push(Permission::new("list", "pods", ns));
+        perms.push(Permission::new("watch", "pods", ns));
+        if pick_deployment {
+            perms.push(Permission::new("list", "deployments.apps", ns));
+        }
+        perms.push(Permission::new("get", "pods/log", ns));
+    }
+    let result = rbac::require(session, &perms).await;
+    pb.finish_and_clear();
+    result
+}
+
+pub async fn run(session: &Session, args: LogArgs) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let LogArgs { pod: pod_arg, deployment: deploy_arg, namespace: namespace_arg, container_select, .. } = args.clone();
+    let patterns = Patterns::compile(&args)?;
+    let tail = parse_tail(&args.tail)?;
+
+    // 0. One session per cluster; streams only carry a cluster tag when there are several
+    let sessions = connect_clusters(session, &args.contexts).await?;
+    let multi = sessions.len() > 1;
+    let tag = |s: &Session| multi.then(|| s.context.clone());
+
+    // 1. Resolve Namespaces
+    let mut scopes: Vec<(&Session, Vec<String>)> = Vec::new();
+    for s in &sessions {
+        if multi {
+            println!("{} {}", "Cluster:".dimmed(), s.context.cyan().bold());
+        }
+        let selected_ns = utils::get_selected_namespaces(s, namespace_arg.clone()).await?;
+        preflight(s, &selected_ns, pod_arg.is_some(), matches!(deploy_arg, Some(None))).await?;
+        scopes.push((s, selected_ns));
+    }
+
+    // 2. Resolve PodOptions (All paths lead to a Vec<PodOption>)
+    let mut pod_options: Vec<PodOption> = Vec::new();
+
+    if let Some(pod_name) = pod_arg {
+        // --- Path A: Direct Pod Name ---
+        // We fetch the pod specifically to get its container list for the -c logic
+        for (s, selected_ns) in &scopes {
+            let pods_api: Api<Pod> = Api::namespaced(s.client.clone(), &selected_ns[0]);
+            let label = format!("Fetching {}...", pod_name);
+            let pb = utils::create_spinner(&label);
+            // Across clusters the pod only has to exist in one of them
+            let fetched = match multi {
+                true => s.retry.call(Some((&pb, &label)), || pods_api.get_opt(&pod_name)).await,
+                false => s.retry.call(Some((&pb, &label)), || pods_api.get(&pod_name)).await.map(Some),
+            };
+            pb.finish_and_clear();
+            let Some(p) = fetched? else {
+                continue;
+            };
+            let containers = p.spec.map(|s| s.containers.into_iter().map(|c| c.name).collect()).unwrap_or_default();
+
+            pod_options.push(PodOption {
+                name: pod_name.clone(),
+                namespace: selected_ns[0].clone(),
+                containers,
+                cluster: tag(s),
+            });
+        }
+    } 
+    else if let Some(deploy_opt) = deploy_arg {
+        // --- Path B: Deployment Mode ---
+        let deploy_name = match deploy_opt {
+            Some(name) => name,
+            None => {
+                let mut deploys: Vec<String> = Vec::new();
+                for (s, selected_ns) in &scopes {
+                    for d in utils::fetch_all_deployments(s, selected_ns.clone()).await? {
+                        if !deploys.contains(&d) {
+                            deploys.push(d);
+                        }
+                    }
+                }
+                inquire::Select::new("Select deployment to tail:", deploys).prompt()?
+            }
+        };
+        
+        // Find pods by label (app=name)
+        let lp = kube::api::ListParams::default().labels(&format!("app={}", deploy_name));
+        let label = format!("Fetching pods of {}...", deploy_name);
+        let pb = utils::create_spinner(&label);
+        for (s, selected_ns) in &scopes {
+            for ns in selected_ns {
+                let api: Api<Pod> = Api::namespaced(s.client.clone(), ns);
+                let pods = s.retry.call(Some((&pb, &label)), || api.list(&lp)).await?;
+                for p in pods.items {
+                    let name = p.metadata.name.clone().unwrap_or_default();
+                    let containers = p.spec.map(|s| s.containers.into_iter().map(|c| c.name).collect()).unwrap_or_default();
+                    pod_options.push(PodOption { name, namespace: ns.clone(), containers, cluster: tag(s) });
+                }
+            }
+        }
+        pb.finish_and_clear();
+    } 
+    else {
+        // --- Path C: Standard Interactive Menu ---
+        let mut available_pods = Vec::new();
+        for (s, selected_ns) in &scopes {
+            let mut pods = utils::fetch_all_pods(s, selected_ns.clone()).await?;
+            pods.iter_mut().for_each(|p| p.cluster = tag(s));
+            available_pods.extend(pods);
+        }
+        pod_options = MultiSelect::new("Select Pods to tail:", available_pods).prompt()?;
+    }
+
+    if pod_options.is_empty() {
+        println!("{}", "No pods found matching your selection.".yellow());
+        return Ok(());
+    }
+
+    // 3. Resolve Containers (This converts Vec<PodOption> -> Vec<(PodOption, String)>)
+    // This handles your -c logic for ALL paths automatically.
+    let final_targets = pick_pods_and_containers(pod_options, container_select).await?;
+
+    // 4. Start Streaming
+    let clients = sessions.iter().map(|s| (tag(s), s.client.clone())).collect();
+    start_log_stream(clients, session.retry, final_targets, patterns, tail, args).await?;
+
+    Ok(())
+}
+
+// --contexts connects to every listed context (in parallel); otherwise the shell's session is used
+async fn connect_clusters(session: &Session, contexts: &[String]) -> Result<Vec<Session>, KlogError> {
+    if contexts.is_empty() {
+        return Ok(vec![session.clone()]);
+    }
+    let pb = utils::create_spinner(&format!("Connecting to {} clusters...", contexts.len()));
+    let connecting = contexts.iter().map(|context| {
+        let connection = ConnectionArgs { context: Some(context.clone()), cluster: None, user: None, ..session.connection.clone() };
+        Session::connect(connection)
+    });
+    let sessions: Result<Vec<Session>, KlogError> = futures::future::join_all(connecting).await.into_iter().collect();
+    pb.finish_and_clear();
+    let mut sessions = sessions?;
+    // A namespace set with `ns` in the shell applies to every cluster, not just the current one
+    if session.namespace != session.context_namespace {
+        for s in &mut sessions {
+            s.namespace = session.namespace.clone();
+        }
+    }
+    Ok(sessions)
+}
+
+// USER SELECTION LOGIC
+async fn pick_pods_and_containers(
+    selected_pods: Vec<PodOption>,
+    force_container_select: bool,
+) -> Result<Vec<(PodOption, String)>, Box<dyn std::error::Error + Send + Sync>> {
+    // let selected_pods = MultiSelect::new("Select Pods to tail:", all_pods).prompt()?;
+    let mut final_targets = Vec::new();
+
+    for p in selected_pods {
+        let container = if force_container_select && p.containers.len() > 1 {
+            Select::new(
+                &format!("Select container for {}:", p.name),
+                p.containers.clone(),
+            )
+            .prompt()?
+        } else {
+            p.containers
+                .first()
+                .cloned()
+                .unwrap_or_else(|| "default".to_string())
+        };
+        final_targets.push((p, container));
+    }
+    Ok(final_targets)
+}
+
+const FOOTER_FPS: u64 = 4;
+const SPLIT_FPS: u64 = 20;
+const SPLIT_PAGE: usize = 20;
+
+type Tui = Terminal<CrosstermBackend<Stdout>>;
+
+// Moves between layouts, entering or leaving the alternate screen as needed
+fn switch_view(guard: &TerminalGuard, tui: &mut Option<Tui>, to: StreamLayout) -> std::io::Result<()> {
+    match (tui.is_some(), to) {
+        (true, StreamLayout::Merged) => {
+            *tui = None;
+            guard.leave_alternate()
+        }
+        (false, StreamLayout::Split | StreamLayout::Events) => {
+            guard.enter_alternate()?;
+            *tui = Some(Terminal::new(CrosstermBackend::new(stdout()))?);
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+async fn start_log_stream(
+    clients: HashMap<Option<String>, Client>,
+    retry: RetryPolicy,
+    targets: Vec<(PodOption, String)>,
+    patterns: Patterns,
+    tail: Option<i64>,
+    args: LogArgs,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let worker_opts = WorkerOptions {
+        previous: args.previous,
+        tail,
+        reconnect: !args.no_reconnect,
+        restart_previous: args.restart_previous,
+        previous_then_current: args.previous_then_current,
+        wait_timeout: (args.wait_timeout > 0).then(|| Duration::from_secs(args.wait_timeout)),
+        retry,
+    };
+    let (tx, mut rx) = tokio::sync::mpsc::channel(render::CHANNEL_DEPTH);
+    let total_streams = targets.len();
+    let streams: Vec<String> = targets.iter().map(|(p, c)| stream_label(p.cluster.as_deref(), &p.namespace, &p.name, c)).collect();
+    // Pod watchers need each cluster's own client
+    let mut pods: Vec<(Client, Vec<PodOption>)> = Vec::new();
+    for (cluster, client) in &clients {
+        let mut group: Vec<PodOption> = Vec::new();
+        for (pod, _) in targets.iter().filter(|(p, _)| p.cluster == *cluster) {
+            if !group.iter().any(|p| p.name == pod.name && p.namespace == pod.namespace) {
+                group.push(pod.clone());
+            }
+        }
+        pods.push((client.clone(), group));
+    }
+
+    // The events layout shows events from here on, however late it is first opened
+    let started = Timestamp::now();
+    // Workers and watchers hold open API connections; dropping the set when this
+    // function returns (however it returns) aborts them all
+    let mut tasks: JoinSet<()> = JoinSet::new();
+
+    // Spawn workers; each one reports back when its stream is over or has failed
+    for (pod, container) in targets {
+        let (tx_c, client_c, opts) = (tx.clone(), clients[&pod.cluster].clone(), worker_opts.clone());
+        tasks.spawn(async move {
+            let (cluster, namespace) = (pod.cluster.clone(), pod.namespace.clone());
+            let (pod_name, container_name) = (pod.name.clone(), container.clone());
+            let event = match worker::tail_logs(client_c, pod, container, tx_c.clone(), opts).await {
+                Ok(reason) => StreamEvent::Ended { cluster, namespace, pod_name, container_name, reason },
+                Err(error) => StreamEvent::Error(StreamError {
+                    cluster,
+                    namespace,
+                    pod_name,
+                    container_name: Some(container_name),
+                    error,
+                    fatal: true,
+                }),
+            };
+            let _ = tx_c.send(event).await;
+        });
+    }
+    for (client, group) in &pods {
+        lifecycle::watch_pods(client.clone(), group, tx.clone(), &mut tasks);
+    }
+    // Kept until the events layout is first opened, which starts the event watchers
+    let mut events_tx = Some(tx);
+
+    // --- 1. ENTER RAW MODE ---
+    // The guard restores the terminal however this function exits
+    let guard = TerminalGuard::enter()?;
+    let shutdown = crate::interrupt::interrupted();
+    tokio::pin!(shutdown);
+
+    let mut pipeline = Pipeline::new(patterns, StreamStats::new(Thresholds {
+        spike_errors: args.spike_threshold,
+        spike_window: Duration::from_secs(args.spike_window),
+        silence: (args.silence > 0).then(|| Duration::from_secs(args.silence)),
+    }));
+    let mut health_tick = tokio::time::interval(Duration::from_secs(1));
+    let mut footer_tick = tokio::time::interval(Duration::from_millis(1000 / FOOTER_FPS));
+    let mut frame_tick = tokio::time::interval(Duration::from_millis(1000 / SPLIT_FPS));
+
+    // Both full-screen views are fed in every layout so toggling shows recent lines right away
+    let mut split = SplitView::new(&streams);
+    let mut events_view = EventsView::new();
+    let mut view = args.layout;
+    let mut tui: Option<Tui> = None;
+    switch_view(&guard, &mut tui, view)?;
+    if view == StreamLayout::Events && let Some(tx) = events_tx.take() {
+        start_event_watchers(&pods, started, tx, &mut tasks);
+    }
+    let mut dirty = true;
+
+    let mut events = EventStream::new();
+    let mut batch = Vec::with_capacity(render::BATCH_MAX);
+    let mut drained = false;
+    let mut ended: Vec<String> = Vec::new();
+    let mut failed: Vec<StreamError> = Vec::new();
+    let mut warnings: usize = 0;
+    // While paused, everything the merged layout would print queues up here and is flushed on resume
+    let mut paused: Option<VecDeque<Output>> = None;
+
+    loop {
+        tokio::select! {
+            n = rx.recv_many(&mut batch, render::BATCH_MAX), if !drained => {
+                if n == 0 {
+                    // Every worker has hung up; keep the loop alive for the keyboard
+                    drained = true;
+                    continue;
+                }
+
+                let now = Instant::now();
+                let mut out = BufWriter::with_capacity(render::OUT_BUFFER, stdout().lock());
+                for event in batch.drain(..) {
+                    let (log, replay) = match event {
+                        StreamEvent::Line(log) => (log, false),
+                        StreamEvent::Replay(log) => (log, true),
+                        StreamEvent::Kube(event) => {
+                            let text = format!("⚠ {} {}: {}", event.pod(), event.reason, event.message);
+                            if events_view.push_event(event) {
+                                // Warnings also land in the log flow, right where they happened
+                                events_view.push_banner(text.clone(), Style::default().fg(Color::Red).add_modifier(Modifier::BOLD));
+                                if tui.is_none() {
+                                    emit(&mut out, &mut paused, Output::Warning(text));
+                                }
+                            }
+                            dirty = true;
+                            continue;
+                        }
+                        StreamEvent::Marker(marker) => {
+                            show_marker(&mut split, &mut events_view, &marker);
+                            if tui.is_none() {
+                                emit(&mut out, &mut paused, Output::Marker(marker));
+                            }
+                            dirty = true;
+                            continue;
+                        }
+                        StreamEvent::Ended { cluster, namespace, pod_name, container_name, reason } => {
+                            let stream = stream_label(cluster.as_deref(), &namespace, &pod_name, &container_name);
+                            pipeline.stats.forget(&stream);
+                            ended.push(stream);
+                            let marker = Marker {
+                                cluster,
+                                namespace,
+                                pod_name,
+                                container_name: Some(container_name),
+                                text: format!("stream ended: {}", reason),
+                            };
+                            show_marker(&mut split, &mut events_view, &marker);
+                            if tui.is_none() {
+                                emit(&mut out, &mut paused, Output::Marker(marker));
+                            }
+                            dirty = true;
+                            continue;
+                        }
+                        StreamEvent::Error(error) => {
+                            show_error(&mut split, &mut events_view, &error);
+                            if tui.is_none() {
+                                emit(&mut out, &mut paused, Output::Error(error.clone()));
+                            }
+                            if error.fatal {
+                                pipeline.stats.forget(&error.stream());
+                                failed.push(error);
+                            } else {
+                                warnings += 1;
+                            }
+                            dirty = true;
+                            continue;
+                        }
+                    };
+
+                    let (alerts, shown) = pipeline.ingest(log, replay, now);
+                    if tui.is_some() {
+                        push_alerts(&mut split, &mut events_view, &alerts);
+                    } else {
+                        for alert in alerts {
+                            emit(&mut out, &mut paused, Output::Alert(alert));
+                        }
+                    }
+                    let Some(log) = shown else { continue };
+
+                    split.push_log(log);
+                    events_view.push_log(log);
+                    if tui.is_some() {
+                        dirty = true;
+                        continue;
+                    }
+
+                    // --- 2. PRINT LOG WITH CARRIAGE RETURN ---
+                    match paused.as_mut() {
+                        Some(queue) => hold(queue, if replay { Output::Replay(log.clone()) } else { Output::Line(log.clone()) }),
+                        None if replay => { let _ = render::write_replay_line(&mut out, log); }
+                        None => { let _ = render::write_line(&mut out, log); }
+                    }
+                }
+                let _ = out.flush();
+            }
+
+            _ = health_tick.tick() => {
+                let alerts = pipeline.stats.check_silence(Instant::now());
+                if tui.is_some() {
+                    dirty |= !alerts.is_empty();
+                    push_alerts(&mut split, &mut events_view, &alerts);
+                } else {
+                    let mut out = stdout().lock();
+                    for alert in alerts {
+                        emit(&mut out, &mut paused, Output::Alert(alert));
+                    }
+                    let _ = out.flush();
+                }
+            }
+
+            _ = frame_tick.tick(), if tui.is_some() && dirty => {
+                let mut status = format!(
+                    "{}/{} streams · {:.1} l/s",
+                    total_streams - ended.len() - failed.len(), total_streams, pipeline.stats.total_rate(Instant::now())
+                );
+                if !failed.is_empty() || warnings > 0 {
+                    status.push_str(&format!(" · {} failed, {} errors", failed.len(), warnings));
+                }
+                if let Some(terminal) = tui.as_mut() {
+                    match view {
+                        StreamLayout::Events => terminal.draw(|frame| events_view.draw(frame, &status))?,
+                        _ => terminal.draw(|frame| split.draw(frame, &status))?,
+                    };
+                }
+                dirty = false;
+            }
+
+            _ = footer_tick.tick(), if tui.is_none() => {
+                let now = Instant::now();
+                footer::draw(&footer::Status {
+                    active: total_streams - ended.len() - failed.len(),
+                    ended: &ended,
+                    failed: failed.len(),
+                    errors: warnings,
+                    rate: pipeline.stats.total_rate(now),
+                    noisiest: pipeline.stats.noisiest(now),
+                    hidden: pipeline.hidden,
+                    history_len: pipeline.history.len(),
+                    history_cap: HISTORY_CAP,
+                    paused: paused.as_ref().map(|q| q.len()),
+                    volume: &pipeline.stats.volume(now),
+                });
+            }
+
+            _ = &mut shutdown => break,
+
+            input = events.next() => {
+                let key = match input {
+                    // Only handle Press events (ignores release events on Windows)
+                    Some(Ok(Event::Key(key))) if key.kind == KeyEventKind::Press => key,
+                    Some(Ok(Event::Resize(_, rows))) => {
+                        if tui.is_some() {
+                            // ratatui picks up the new size on the next draw
+                            dirty = true;
+                        } else {
+                            footer::set_region(rows);
+                            footer_tick.reset_immediately();
+                        }
+                        continue;
+                    }
+                    Some(Ok(_)) => continue,
+                    Some(Err(e)) => return Err(e.into()),
+                    None => break,
+                };
+
+                match key.code {
+                    KeyCode::Char('q') => break,
+                    // Raw mode delivers Ctrl-C as a key instead of SIGINT
+                    KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => break,
+
+                    KeyCode::Char(c @ ('v' | 'e')) => {
+                        let target = if c == 'v' { StreamLayout::Split } else { StreamLayout::Events };
+                        view = if view == target { StreamLayout::Merged } else { target };
+                        switch_view(&guard, &mut tui, view)?;
+                        if view == StreamLayout::Events && let Some(tx) = events_tx.take() {
+                            start_event_watchers(&pods, started, tx, &mut tasks);
+                        }
+                        footer_tick.reset_immediately();
+                        dirty = true;
+                    }
+                    _ if view == StreamLayout::Split => {
+                        match key.code {
+                            KeyCode::Tab => split.focus_next(),
+                            KeyCode::BackTab => split.focus_prev(),
+                            KeyCode::Up => split.scroll_up(1),
+                            KeyCode::Down => split.scroll_down(1),
+                            KeyCode::PageUp => split.scroll_up(SPLIT_PAGE),
+                            KeyCode::PageDown => split.scroll_down(SPLIT_PAGE),
+                            KeyCode::Home => split.scroll_top(),
+                            KeyCode::End => split.follow(),
+                            _ => continue,
+                        }
+                        dirty = true;
+                    }
+                    _ if view == StreamLayout::Events => {}
+
+                    KeyCode::Char('p') => match paused.take() {
+                        Some(queue) => {
+                            let mut out = BufWriter::with_capacity(render::OUT_BUFFER, stdout().lock());
+                            for output in &queue { let _ = write_output(&mut out, output); }
+                            let _ = out.flush();
+                        }
+                        None => paused = Some(VecDeque::new()),
+                    },
+                    
+                    KeyCode::Char('s') => {
+                        // --- 3. TEMPORARILY EXIT RAW MODE FOR SEARCH ---
+                        // inquire reads the terminal itself, so stop our reader until it's done
+                        drop(events);
+                        guard.suspend()?;
+                        println!("\n{}", " --- ⏸️  STREAM PAUSED --- ".on_yellow().black());
+
+                        let query = inquire::Text::new("Search history:").prompt()?;
+                        let matches: Vec<LogMessage> = pipeline.history.iter()
+                            .filter(|h| h.message.to_lowercase().contains(&query.to_lowercase()))
+                            .cloned().collect();
+
+                        if !matches.is_empty() {
+                            // Use our custom help message here
+                            let _ = Select::new("Search Results:", matches)
+                                .with_help_message("↑↓ to scroll through history, Enter to return to live logs")
+                                .prompt();
+                        }
+
+                        println!("{}", " ---  RESUMING --- ".on_green().black());
+                        
+                        // RE-ENTER RAW MODE
+                        guard.resume()?;
+                        events = EventStream::new();
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    // --- 4. CLEANUP ---
+    // Restore the terminal first so the summary prints as normal output
+    drop(tui);
+    drop(guard);
+    print_failures(&failed, total_streams);
+    // The first fatal failure decides the exit code; the streams already ran, so no retry
+    match failed.into_iter().next() {
+        Some(first) => Err(NotRetryable(first.error).into()),
+        None => Ok(()),
+    }
+}
+
+// Errors go to every layout, like markers, and stand out from regular output
+fn show_error(split: &mut SplitView, events_view: &mut EventsView, error: &StreamError) {
+    let stream = error.stream();
+    let text = format!("✖ {}", error.error);
+    let style = Style::default().fg(Color::Red).add_modifier(Modifier::BOLD);
+    split.push_banner(&stream, text.clone(), style);
+    events_view.push_banner(format!("[{}] {}", stream, text), style);
+}
+
+fn print_failures(failed: &[StreamError], total: usize) {
+    if failed.is_empty() {
+        return;
+    }
+    println!("{}", format!("✖ {} of {} streams failed:", failed.len(), total).red().bold());
+    for error in failed {
+        println!("  {} {}", error.stream().bold(), error.error.to_string().red());
+    }
+}
+
+fn start_event_watchers(
+    pods: &[(Client, Vec<PodOption>)],
+    started: Timestamp,
+    tx: tokio::sync::mpsc::Sender<StreamEvent>,
+    tasks: &mut JoinSet<()>,
+) {
+    for (client, group) in pods {
+        events::watch_events(client.clone(), group, started, tx.clone(), tasks);
+    }
+}
+
+// Stream label and plain text for a health alert, shared by both layouts
+fn describe_alert(alert: &Alert) -> (&str, String) {
+    match alert {
+        Alert::ErrorSpike { stream, errors, window } => (
+            stream,
+            format!("🔥 ERROR SPIKE [{}] {} errors in the last {}s", stream, errors, window.as_secs()),
+        ),
+        Alert::Silent { stream, idle } => (
+            stream,
+            format!("🔇 SILENT [{}] no output for {}s (possible hang)", stream, idle.as_secs()),
+        ),
+        Alert::Resumed { stream, idle } => (
+            stream,
+            format!("🔊 RESUMED [{}] logging again after {}s", stream, idle.as_secs()),
+        ),
+    }
+}
+
+fn write_alert<W: Write>(out: &mut W, alert: &Alert) -> std::io::Result<()> {
+    let (_, text) = describe_alert(alert);
+    let text = format!(" {} ", text);
+    let banner = match alert {
+        Alert::ErrorSpike { .. } => text.on_red().white().bold(),
+        Alert::Silent { .. } => text.on_yellow().black(),
+        Alert::Resumed { .. } => text.on_green().black(),
+    };
+    write!(out, "\r{}\n", banner)
+}
+
+// --- MERGED OUTPUT ---
+// Everything the merged layout prints, so a pause can hold it back and
+// replay it on resume in the order it arrived, styling included.
+enum Output {
+    Line(LogMessage),
+    Replay(LogMessage),
+    Marker(Marker),
+    Error(StreamError),
+    Alert(Alert),
+    Warning(String),
+}
+
+fn write_output<W: Write>(out: &mut W, output: &Output) -> std::io::Result<()> {
+    match output {
+        Output::Line(log) => render::write_line(out, log),
+        Output::Replay(log) => render::write_replay_line(out, log),
+        Output::Marker(marker) => render::write_marker(out, marker),
+        Output::Error(error) => render::write_error(out, error),
+        Output::Alert(alert) => write_alert(out, alert),
+        Output::Warning(text) => write!(out, "\r{}\n", text.red().bold()),
+    }
+}
+
+// Prints right away, or queues while paused
+fn emit<W: Write>(out: &mut W, paused: &mut Option<VecDeque<Output>>, output: Output) {
+    match paused.as_mut() {
+        Some(queue) => hold(queue, output),
+        None => { let _ = write_output(out, &output); }
+    }
+}
+
+fn hold(queue: &mut VecDeque<Output>, output: Output) {
+    if queue.len() >= HISTORY_CAP { queue.pop_front(); }
+    queue.push_back(output);
+}
+
+// Markers go to every layout so switching views doesn't lose them
+fn show_marker(split: &mut SplitView, events_view: &mut EventsView, marker: &Marker) {
+    split.push_marker(marker);
+    events_view.push_banner(
+        format!("[{}] ── {} ──", marker.stream(), marker.text),
+        Style::default().fg(Color::LightBlue).add_modifier(Modifier::ITALIC),
+    );
+}
+
+fn push_alerts(split: &mut SplitView, events_view: &mut EventsView, alerts: &[Alert]) {
+    for alert in alerts {
+        let (stream, text) = describe_alert(alert);
+        let style = match alert {
+            Alert::ErrorSpike { .. } => Style::default().bg(Color::Red).fg(Color::White).add_modifier(Modifier::BOLD),
+            Alert::Silent { .. } => Style::default().bg(Color::Yellow).fg(Color::Black),
+            Alert::Resumed { .. } => Style::default().bg(Color::Green).fg(Color::Black),
+        };
+        events_view.push_banner(text.clone(), style);
+        split.push_banner(stream, text, style);
+    }
+}
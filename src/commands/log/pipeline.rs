@@ -0,0 +1,56 @@
+//! Per-line work of the stream loop: health stats, filters and history.
+//!
+//! Every line a worker sends goes through `Pipeline::ingest` before it is
+//! rendered, so this is the hot path `cargo bench --bench render` measures
+//! together with the renderer.
+
+use super::Patterns;
+use super::stats::{Alert, StreamStats};
+use crate::models::LogMessage;
+use regex::Regex;
+use std::collections::VecDeque;
+use std::time::Instant;
+
+/// Lines kept for search, visible or not.
+pub const HISTORY_CAP: usize = 1000;
+
+pub struct Pipeline {
+    pub patterns: Patterns,
+    pub stats: StreamStats,
+    pub history: VecDeque<LogMessage>,
+    /// Lines dropped by --filter/--exclude so far.
+    pub hidden: u64,
+}
+
+impl Pipeline {
+    pub fn new(patterns: Patterns, stats: StreamStats) -> Self {
+        Self { patterns, stats, history: VecDeque::with_capacity(HISTORY_CAP), hidden: 0 }
+    }
+
+    /// Records one line and keeps it in history. Returns the alerts it raised and,
+    /// if the filters let it through, the line to show.
+    pub fn ingest(&mut self, log: LogMessage, replay: bool, now: Instant) -> (Vec<Alert>, Option<&LogMessage>) {
+        // Replayed lines are old news; they must not skew rates or trigger alerts
+        let alerts = if replay {
+            Vec::new()
+        } else {
+            self.stats.record(&log.stream(), self.patterns.error.is_match(&log.message), now)
+        };
+
+        let visible = !self.patterns.exclude.as_ref().is_some_and(|re| matches(re, &log))
+            && self.patterns.filter.as_ref().is_none_or(|re| matches(re, &log));
+
+        if self.history.len() >= HISTORY_CAP { self.history.pop_front(); }
+        self.history.push_back(log);
+        if !visible {
+            self.hidden += 1;
+            return (alerts, None);
+        }
+        (alerts, self.history.back())
+    }
+}
+
+// Filters also match the cluster name, so `-e us-east` drops a whole region
+fn matches(re: &Regex, log: &LogMessage) -> bool {
+    re.is_match(&log.message) || log.cluster.as_deref().is_some_and(|c| re.is_match(c))
+}
@@ -0,0 +1,209 @@
+use crate::models::{LogMessage, Marker};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+};
+use std::collections::{HashMap, VecDeque};
+
+// Per-pane scrollback; older lines are still in the merged history
+const PANE_SCROLLBACK: usize = 2000;
+
+struct Pane {
+    title: String,
+    lines: VecDeque<Line<'static>>,
+    // Lines scrolled up from the bottom; 0 means following the tail
+    scroll: usize,
+}
+
+/// One tiled pane per `(cluster, namespace, pod, container)` target with its own scrollback.
+pub struct SplitView {
+    panes: Vec<Pane>,
+    index: HashMap<String, usize>,
+    focus: usize,
+}
+
+impl SplitView {
+    pub fn new(streams: &[String]) -> Self {
+        let panes = streams
+            .iter()
+            .map(|s| Pane { title: s.clone(), lines: VecDeque::new(), scroll: 0 })
+            .collect();
+        let index = streams.iter().enumerate().map(|(i, s)| (s.clone(), i)).collect();
+        Self { panes, index, focus: 0 }
+    }
+
+    pub fn push_log(&mut self, log: &LogMessage) {
+        self.push(&log.stream(), Line::raw(log.message.clone()));
+    }
+
+    /// A pod label instead of a stream label puts the banner in every pane of that pod.
+    pub fn push_banner(&mut self, stream: &str, text: String, style: Style) {
+        let line = Line::from(Span::styled(text, style));
+        let pod_prefix = format!("{}/", stream);
+        let streams: Vec<String> = self
+            .panes
+            .iter()
+            .filter(|p| p.title == stream || p.title.starts_with(&pod_prefix))
+            .map(|p| p.title.clone())
+            .collect();
+        for stream in streams {
+            self.push(&stream, line.clone());
+        }
+    }
+
+    /// Markers without a container go to every pane of that pod.
+    pub fn push_marker(&mut self, marker: &Marker) {
+        let line = Line::from(Span::styled(
+            format!("── {} ──", marker.text),
+            Style::default().fg(Color::LightBlue).add_modifier(Modifier::ITALIC),
+        ));
+        // A pod-wide marker's label ("[cluster/]namespace/pod") prefixes each of its streams
+        let label = marker.stream();
+        let pod_prefix = format!("{}/", label);
+        let streams: Vec<String> = self
+            .panes
+            .iter()
+            .filter(|p| match marker.container_name {
+                Some(_) => p.title == label,
+                None => p.title.starts_with(&pod_prefix),
+            })
+            .map(|p| p.title.clone())
+            .collect();
+        for stream in streams {
+            self.push(&stream, line.clone());
+        }
+    }
+
+    fn push(&mut self, stream: &str, line: Line<'static>) {
+        let Some(&i) = self.index.get(stream) else { return };
+        let pane = &mut self.panes[i];
+        if pane.lines.len() >= PANE_SCROLLBACK {
+            pane.lines.pop_front();
+        }
+        pane.lines.push_back(line);
+        // Keep a scrolled-back pane pinned to what the user is reading
+        if pane.scroll > 0 {
+            pane.scroll = (pane.scroll + 1).min(pane.lines.len());
+        }
+    }
+
+    pub fn focus_next(&mut self) {
+        self.focus = (self.focus + 1) % self.panes.len().max(1);
+    }
+
+    pub fn focus_prev(&mut self) {
+        let n = self.panes.len().max(1);
+        self.focus = (self.focus + n - 1) % n;
+    }
+
+    pub fn scroll_up(&mut self, n: usize) {
+        if let Some(pane) = self.panes.get_mut(self.focus) {
+            pane.scroll = (pane.scroll + n).min(pane.lines.len());
+        }
+    }
+
+    pub fn scroll_down(&mut self, n: usize) {
+        if let Some(pane) = self.panes.get_mut(self.focus) {
+            pane.scroll = pane.scroll.saturating_sub(n);
+        }
+    }
+
+    pub fn scroll_top(&mut self) {
+        if let Some(pane) = self.panes.get_mut(self.focus) {
+            pane.scroll = pane.lines.len();
+        }
+    }
+
+    pub fn follow(&mut self) {
+        if let Some(pane) = self.panes.get_mut(self.focus) {
+            pane.scroll = 0;
+        }
+    }
+
+    pub fn draw(&self, frame: &mut Frame, status: &str) {
+        let [body, footer] = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(1)])
+            .areas(frame.size());
+
+        for (i, area) in tile(body, self.panes.len()).into_iter().enumerate() {
+            self.draw_pane(frame, i, area);
+        }
+
+        let keys = Line::from(vec![
+            Span::styled(format!(" {} ", status), Style::default().fg(Color::Green)),
+            Span::styled("| ", Style::default().fg(Color::DarkGray)),
+            Span::styled("Tab", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            Span::styled(" Focus  ", Style::default().fg(Color::DarkGray)),
+            Span::styled("↑↓ PgUp PgDn", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            Span::styled(" Scroll  ", Style::default().fg(Color::DarkGray)),
+            Span::styled("End", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            Span::styled(" Follow  ", Style::default().fg(Color::DarkGray)),
+            Span::styled("v", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            Span::styled(" Merged view  ", Style::default().fg(Color::DarkGray)),
+            Span::styled("q", Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)),
+            Span::styled(" Quit", Style::default().fg(Color::DarkGray)),
+        ]);
+        frame.render_widget(Paragraph::new(keys), footer);
+    }
+
+    fn draw_pane(&self, frame: &mut Frame, i: usize, area: Rect) {
+        let pane = &self.panes[i];
+        let focused = i == self.focus;
+
+        let height = area.height.saturating_sub(2) as usize;
+        let len = pane.lines.len();
+        // Scrolling past the top keeps the first screenful in view
+        let end = (len - pane.scroll.min(len)).max(height.min(len));
+        let start = end.saturating_sub(height);
+        let visible: Vec<Line> = pane.lines.range(start..end).cloned().collect();
+
+        let position = if pane.scroll == 0 {
+            "following".to_string()
+        } else {
+            format!("↑ {} lines", pane.scroll)
+        };
+        let border = if focused {
+            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(border)
+            .title(format!(" {} ", pane.title))
+            .title(ratatui::widgets::block::Title::from(format!(" {} ", position))
+                .alignment(ratatui::layout::Alignment::Right));
+
+        frame.render_widget(Paragraph::new(visible).block(block), area);
+    }
+}
+
+// Up to three panes stack vertically (log lines are wide); beyond that two columns
+fn tile(area: Rect, n: usize) -> Vec<Rect> {
+    if n == 0 {
+        return Vec::new();
+    }
+    let cols = if n <= 3 { 1 } else { 2 };
+    let rows = n.div_ceil(cols);
+
+    let row_areas = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(vec![Constraint::Ratio(1, rows as u32); rows])
+        .split(area);
+
+    let mut tiles = Vec::with_capacity(n);
+    for (r, row) in row_areas.iter().enumerate() {
+        // The last row may hold a single pane spanning the full width
+        let in_row = (n - r * cols).min(cols);
+        let col_areas = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(vec![Constraint::Ratio(1, in_row as u32); in_row])
+            .split(*row);
+        tiles.extend(col_areas.iter().copied());
+    }
+    tiles
+}
@@ -0,0 +1,202 @@
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+// How much per-second history we keep for every stream
+pub const RETENTION_SECS: u64 = 60;
+// Rates are averaged over the last few completed seconds so they don't flicker
+const RATE_WINDOW_SECS: u64 = 5;
+
+#[derive(Clone, Copy)]
+struct Bucket {
+    second: u64,
+    lines: u32,
+    errors: u32,
+}
+
+struct PodStats {
+    buckets: VecDeque<Bucket>,
+    last_seen: Instant,
+    spiking: bool,
+    silent: bool,
+}
+
+/// Thresholds for the health checks, taken from the log command flags.
+#[derive(Clone, Copy)]
+pub struct Thresholds {
+    pub spike_errors: usize,
+    pub spike_window: Duration,
+    pub silence: Option<Duration>,
+}
+
+pub enum Alert {
+    ErrorSpike { stream: String, errors: u32, window: Duration },
+    Silent { stream: String, idle: Duration },
+    Resumed { stream: String, idle: Duration },
+}
+
+// --- PER-STREAM SLIDING WINDOWS ---
+// Lines are counted into one bucket per second, so recording stays O(1)
+// no matter how chatty a pod is.
+pub struct StreamStats {
+    started: Instant,
+    thresholds: Thresholds,
+    streams: HashMap<String, PodStats>,
+    totals: VecDeque<Bucket>,
+}
+
+impl StreamStats {
+    pub fn new(thresholds: Thresholds) -> Self {
+        Self {
+            started: Instant::now(),
+            thresholds,
+            streams: HashMap::new(),
+            totals: VecDeque::new(),
+        }
+    }
+
+    fn second_of(&self, at: Instant) -> u64 {
+        at.duration_since(self.started).as_secs()
+    }
+
+    /// Counts one line for `stream`. Returns alerts raised by this line
+    /// (an error spike, or a silent stream coming back).
+    pub fn record(&mut self, stream: &str, is_error: bool, now: Instant) -> Vec<Alert> {
+        let second = self.second_of(now);
+        let window_secs = self.thresholds.spike_window.as_secs().max(1);
+        let spike_errors = self.thresholds.spike_errors;
+
+        // Only a stream's first line pays for an owned key
+        if !self.streams.contains_key(stream) {
+            let fresh = PodStats {
+                buckets: VecDeque::new(),
+                last_seen: now,
+                spiking: false,
+                silent: false,
+            };
+            self.streams.insert(stream.to_string(), fresh);
+        }
+        let Some(stats) = self.streams.get_mut(stream) else { return Vec::new() };
+
+        push_line(&mut self.totals, second, is_error);
+
+        let mut alerts = Vec::new();
+
+        if stats.silent {
+            stats.silent = false;
+            alerts.push(Alert::Resumed {
+                stream: stream.to_string(),
+                idle: now.duration_since(stats.last_seen),
+            });
+        }
+        stats.last_seen = now;
+
+        push_line(&mut stats.buckets, second, is_error);
+
+        let errors = sum_since(&stats.buckets, second, window_secs, |b| b.errors);
+        if spike_errors > 0 && errors as usize >= spike_errors {
+            if !stats.spiking {
+                stats.spiking = true;
+                alerts.push(Alert::ErrorSpike {
+                    stream: stream.to_string(),
+                    errors,
+                    window: self.thresholds.spike_window,
+                });
+            }
+        } else if (errors as usize) < (spike_errors / 2).max(1) {
+            // Re-arm only once the rate has clearly settled down (for a threshold of 1: no errors left)
+            stats.spiking = false;
+        }
+
+        alerts
+    }
+
+    /// Stops tracking a stream that ended or failed, so it isn't reported as silent.
+    pub fn forget(&mut self, stream: &str) {
+        self.streams.remove(stream);
+    }
+
+    /// Periodic check for streams that stopped logging.
+    pub fn check_silence(&mut self, now: Instant) -> Vec<Alert> {
+        let Some(limit) = self.thresholds.silence else {
+            return Vec::new();
+        };
+
+        let mut alerts = Vec::new();
+        for (stream, stats) in self.streams.iter_mut() {
+            let idle = now.duration_since(stats.last_seen);
+            if !stats.silent && idle >= limit {
+                stats.silent = true;
+                alerts.push(Alert::Silent {
+                    stream: stream.clone(),
+                    idle,
+                });
+            }
+        }
+        alerts
+    }
+
+    /// Lines per second across all streams.
+    pub fn total_rate(&self, now: Instant) -> f64 {
+        rate(&self.totals, self.second_of(now))
+    }
+
+    /// The stream with the highest line rate right now, if any is logging.
+    pub fn noisiest(&self, now: Instant) -> Option<(&str, f64)> {
+        let second = self.second_of(now);
+        self.streams
+            .iter()
+            .map(|(name, s)| (name.as_str(), rate(&s.buckets, second)))
+            .filter(|(_, r)| *r > 0.0)
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+    }
+
+    /// Total lines per second for the last minute, oldest first.
+    pub fn volume(&self, now: Instant) -> Vec<u32> {
+        let second = self.second_of(now);
+        let first = (second + 1).saturating_sub(RETENTION_SECS);
+        let mut counts = vec![0; (second + 1 - first) as usize];
+        for b in self.totals.iter().filter(|b| b.second >= first) {
+            counts[(b.second - first) as usize] = b.lines;
+        }
+        counts
+    }
+}
+
+fn push_line(buckets: &mut VecDeque<Bucket>, second: u64, is_error: bool) {
+    match buckets.back_mut() {
+        Some(b) if b.second == second => {
+            b.lines += 1;
+            b.errors += is_error as u32;
+        }
+        _ => buckets.push_back(Bucket {
+            second,
+            lines: 1,
+            errors: is_error as u32,
+        }),
+    }
+    while buckets
+        .front()
+        .is_some_and(|b| b.second + RETENTION_SECS <= second)
+    {
+        buckets.pop_front();
+    }
+}
+
+// Average over the completed seconds only; the current one is still filling up
+fn rate(buckets: &VecDeque<Bucket>, now: u64) -> f64 {
+    let lines: u32 = buckets
+        .iter()
+        .filter(|b| b.second < now && b.second + RATE_WINDOW_SECS >= now)
+        .map(|b| b.lines)
+        .sum();
+    lines as f64 / RATE_WINDOW_SECS.min(now.max(1)) as f64
+}
+
+fn sum_since(buckets: &VecDeque<Bucket>, now: u64, window: u64, f: impl Fn(&Bucket) -> u32) -> u32 {
+    buckets
+        .iter()
+        .rev()
+        .take_while(|b| b.second + window > now)
+        .map(f)
+        .sum()
+}
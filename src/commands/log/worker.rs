@@ -0,0 +1,414 @@
+use crate::error::KlogError;
+use crate::models::{LogMessage, Marker, PodOption, StreamError, StreamEvent};
+use crate::retry::RetryPolicy;
+use futures::{AsyncBufReadExt, StreamExt};
+use jiff::Timestamp;
+use k8s_openapi::api::core::v1::Pod;
+use kube::{
+    Api, Client,
+    api::LogParams,
+    runtime::{WatchStreamExt, watcher},
+};
+use std::collections::HashSet;
+use std::time::Duration;
+use tokio::sync::mpsc::Sender;
+
+const BACKOFF_INITIAL: Duration = Duration::from_millis(500);
+const BACKOFF_MAX: Duration = Duration::from_secs(30);
+
+/// Per-stream settings taken from the log command flags.
+#[derive(Clone)]
+pub struct WorkerOptions {
+    pub previous: bool,
+    /// Lines of history to start from; `None` means everything
+    pub tail: Option<i64>,
+    pub reconnect: bool,
+    /// Replay this many lines of the terminated instance whenever the container restarts
+    pub restart_previous: Option<u32>,
+    /// Replay the previous instance (if any) before following the current one
+    pub previous_then_current: bool,
+    /// How long to wait for the container to start before giving up; `None` waits forever
+    pub wait_timeout: Option<Duration>,
+    /// Applied to the pod lookups and replays; the log stream itself reconnects instead
+    pub retry: RetryPolicy,
+}
+
+// Newest timestamp delivered so far, plus the lines seen at exactly that instant.
+// A resumed stream starts at `since_time` (second precision) and replays some
+// lines we already printed; this is what filters them out.
+#[derive(Default)]
+struct Resume {
+    last: Option<Timestamp>,
+    at_last: HashSet<String>,
+}
+
+impl Resume {
+    fn accept(&mut self, ts: Timestamp, line: &str) -> bool {
+        match self.last {
+            Some(last) if ts < last => false,
+            Some(last) if ts == last => self.at_last.insert(line.to_string()),
+            _ => {
+                self.last = Some(ts);
+                self.at_last.clear();
+                self.at_last.insert(line.to_string());
+                true
+            }
+        }
+    }
+}
+
+/// Follows one container until its pod is gone or finished, reconnecting with
+/// exponential backoff whenever the API server drops the stream. Returns why
+/// the stream ended, or the failure that stopped it.
+pub async fn tail_logs(
+    client: Client,
+    pod: PodOption,
+    container: String,
+    tx: Sender<StreamEvent>,
+    opts: WorkerOptions,
+) -> Result<String, KlogError> {
+    let pods: Api<Pod> = Api::namespaced(client, &pod.namespace);
+    let tail_setting = opts.tail;
+
+    let mut resume = Resume::default();
+    let mut backoff = BACKOFF_INITIAL;
+    let mut attached = false;
+
+    // Pending pods and containers still being created have no logs to attach to yet
+    if !opts.previous {
+        let waiting = wait_for_container(&pods, &pod, &container, &tx);
+        match opts.wait_timeout {
+            Some(limit) => tokio::time::timeout(limit, waiting)
+                .await
+                .map_err(|_| {
+                    KlogError::Other(format!("container {} did not start within {}s", container, limit.as_secs()))
+                })??,
+            None => waiting.await?,
+        }
+    }
+
+    let mut restarts = match opts.retry.call(None, || pods.get_opt(&pod.name)).await {
+        Ok(Some(p)) => restart_count(&p, &container),
+        _ => 0,
+    };
+    if opts.previous_then_current && !opts.previous {
+        if restarts > 0 {
+            let label = format!("previous instance (restart #{})", restarts);
+            if !replay_previous(&pods, &opts.retry, &pod, &container, tail_setting, &label, &tx).await {
+                return Ok("closed".to_string());
+            }
+        } else {
+            let text = "no previous instance, following current".to_string();
+            let _ = tx.send(marker(&pod, &container, text)).await;
+        }
+    }
+
+    loop {
+        // A reconnect is our cue to check whether the container restarted in between
+        if attached
+            && let Some(lines) = opts.restart_previous
+            && let Ok(Some(p)) = opts.retry.call(None, || pods.get_opt(&pod.name)).await
+            && restart_count(&p, &container) > restarts
+        {
+            restarts = restart_count(&p, &container);
+            let label = format!("terminated instance before restart #{}", restarts);
+            if !replay_previous(&pods, &opts.retry, &pod, &container, Some(i64::from(lines)), &label, &tx).await {
+                return Ok("closed".to_string());
+            }
+        }
+
+        let lp = LogParams {
+            follow: true,
+            // On resume the timestamp replaces the tail so nothing in between is lost
+            tail_lines: if resume.last.is_none() { tail_setting } else { None },
+            since_time: resume.last,
+            timestamps: true,
+            container: Some(container.clone()),
+            previous: opts.previous,
+            ..LogParams::default()
+        };
+
+        let interruption = match pods.log_stream(&pod.name, &lp).await {
+            Ok(log_stream) => {
+                attached = true;
+                let mut lines = log_stream.lines();
+                let mut delivered = false;
+                let mut failure = None;
+
+                while let Some(line_result) = lines.next().await {
+                    let line = match line_result {
+                        Ok(line) => line,
+                        Err(e) => {
+                            failure = Some(e.to_string());
+                            break;
+                        }
+                    };
+                    let message = match split_timestamp(&line) {
+                        Some((ts, rest)) => {
+                            if !resume.accept(ts, rest) {
+                                continue;
+                            }
+                            rest.to_string()
+                        }
+                        None => line,
+                    };
+
+                    delivered = true;
+                    let msg = LogMessage {
+                        cluster: pod.cluster.clone(),
+                        namespace: pod.namespace.clone(),
+                        pod_name: pod.name.clone(),
+                        container_name: container.clone(),
+                        message,
+                    };
+                    if tx.send(StreamEvent::Line(msg)).await.is_err() {
+                        return Ok("closed".to_string());
+                    }
+                }
+
+                // A connection that produced output earns a fresh backoff
+                if delivered {
+                    backoff = BACKOFF_INITIAL;
+                }
+                failure
+            }
+            // Client errors (missing container, forbidden, ...) won't fix themselves. Once
+            // attached they usually mean a restarted container isn't running yet, so retry.
+            Err(kube::Error::Api(status)) if !attached && status.code < 500 && status.code != 429 => {
+                return Err(kube::Error::Api(status).into());
+            }
+            Err(e) => Some(e.to_string()),
+        };
+
+        // Previous-instance logs are finite, so EOF really is the end
+        if opts.previous || !opts.reconnect {
+            return Ok(interruption.unwrap_or_else(|| "EOF".to_string()));
+        }
+
+        // A clean EOF also happens on container restarts; only stop once the pod is
+        // done, or this container exited for good while the rest of the pod runs on
+        if interruption.is_none() {
+            match opts.retry.call(None, || pods.get_opt(&pod.name)).await {
+                Ok(None) => return Ok("pod deleted".to_string()),
+                Ok(Some(p)) => {
+                    if let Some(code) = final_exit_code(&p, &container) {
+                        return Ok(format!("container exited (code {})", code));
+                    }
+                    let phase = p.status.and_then(|s| s.phase).unwrap_or_default();
+                    if phase == "Succeeded" || phase == "Failed" {
+                        return Ok(format!("pod {}", phase.to_lowercase()));
+                    }
+                }
+                Err(_) => {}
+            }
+        }
+
+        let reason = interruption.unwrap_or_else(|| "EOF".to_string());
+        let text = format!("stream interrupted ({}), reconnecting in {:.1}s", reason, backoff.as_secs_f64());
+        if tx.send(marker(&pod, &container, text)).await.is_err() {
+            return Ok("closed".to_string());
+        }
+
+        tokio::time::sleep(backoff).await;
+        backoff = (backoff * 2).min(BACKOFF_MAX);
+    }
+}
+
+// Watches the pod until `container` is running (or already finished, which still
+// has logs), reporting each new waiting reason inline as it changes.
+async fn wait_for_container(
+    pods: &Api<Pod>,
+    pod: &PodOption,
+    container: &str,
+    tx: &Sender<StreamEvent>,
+) -> Result<(), KlogError> {
+    let config = watcher::Config::default().fields(&format!("metadata.name={}", pod.name));
+    let mut stream = watcher(pods.clone(), config).default_backoff().boxed();
+    let mut last_reason = String::new();
+
+    while let Some(event) = stream.next().await {
+        let p = match event {
+            Ok(watcher::Event::Apply(p) | watcher::Event::InitApply(p)) => p,
+            Ok(watcher::Event::Delete(_)) => {
+                let message = format!("pod {} was deleted before {} started", pod.name, container);
+                return Err(KlogError::NotFound { kind: "pod".to_string(), name: pod.name.clone(), message });
+            }
+            // RBAC won't change while we wait; anything else is retried by the backoff
+            Err(e) => match forbidden(&e) {
+                Some(denied) => return Err(denied),
+                None => continue,
+            },
+            _ => continue,
+        };
+
+        let reason = match waiting_reason(&p, container) {
+            None => return Ok(()),
+            Some(reason) => reason,
+        };
+        if reason != last_reason {
+            if tx.send(marker(pod, container, format!("waiting: {}", reason))).await.is_err() {
+                return Ok(());
+            }
+            last_reason = reason;
+        }
+    }
+    let message = format!("lost the watch on pod {} while waiting for {}", pod.name, container);
+    Err(KlogError::Unreachable(message))
+}
+
+/// The watcher error as a `KlogError` if RBAC denied the list or watch behind it.
+pub fn forbidden(err: &watcher::Error) -> Option<KlogError> {
+    let status = match err {
+        watcher::Error::InitialListFailed(kube::Error::Api(status))
+        | watcher::Error::WatchStartFailed(kube::Error::Api(status))
+        | watcher::Error::WatchFailed(kube::Error::Api(status)) => status,
+        watcher::Error::WatchError(status) => status,
+        _ => return None,
+    };
+    (status.code == 403).then(|| KlogError::from_status(status))
+}
+
+// `None` once the container has logs to read, otherwise why it hasn't started
+fn waiting_reason(p: &Pod, container: &str) -> Option<String> {
+    let status = p.status.as_ref();
+    let cs = status
+        .and_then(|s| s.container_statuses.as_ref())
+        .and_then(|cs| cs.iter().find(|c| c.name == container));
+
+    match cs.and_then(|c| c.state.as_ref()) {
+        Some(state) if state.running.is_some() || state.terminated.is_some() => None,
+        Some(state) => {
+            let waiting = state.waiting.as_ref();
+            let reason = waiting.and_then(|w| w.reason.clone()).unwrap_or_else(|| "ContainerCreating".to_string());
+            Some(match waiting.and_then(|w| w.message.clone()) {
+                Some(message) => format!("{} ({})", reason, message),
+                None => reason,
+            })
+        }
+        // No container status yet: usually still being scheduled
+        None => {
+            let phase = status.and_then(|s| s.phase.clone()).unwrap_or_else(|| "Pending".to_string());
+            let unscheduled = status
+                .and_then(|s| s.conditions.as_ref())
+                .and_then(|c| c.iter().find(|c| c.type_ == "PodScheduled" && c.status == "False"));
+            Some(match unscheduled {
+                Some(c) => format!(
+                    "pod {} · {} ({})",
+                    phase,
+                    c.reason.clone().unwrap_or_default(),
+                    c.message.clone().unwrap_or_default()
+                ),
+                None => format!("pod {}", phase),
+            })
+        }
+    }
+}
+
+// The exit code of `container` if it has terminated and the kubelet won't start it
+// again: restartPolicy Never, OnFailure after a clean exit, or a finished init container
+fn final_exit_code(p: &Pod, container: &str) -> Option<i32> {
+    let status = p.status.as_ref()?;
+    let cs = status
+        .init_container_statuses
+        .iter()
+        .flatten()
+        .chain(status.container_statuses.iter().flatten())
+        .find(|c| c.name == container)?;
+    let code = cs.state.as_ref()?.terminated.as_ref()?.exit_code;
+
+    let spec = p.spec.as_ref()?;
+    let is_init = spec.init_containers.iter().flatten().any(|c| c.name == container);
+    let own_policy = spec
+        .init_containers
+        .iter()
+        .flatten()
+        .chain(&spec.containers)
+        .find(|c| c.name == container)
+        .and_then(|c| c.restart_policy.as_deref());
+    let restarts = match own_policy.or(spec.restart_policy.as_deref()).unwrap_or("Always") {
+        "Never" => false,
+        "OnFailure" => code != 0,
+        // Init containers (but not sidecars) only ever rerun after a failure
+        _ if is_init && own_policy.is_none() => code != 0,
+        _ => true,
+    };
+    (!restarts).then_some(code)
+}
+
+fn restart_count(p: &Pod, container: &str) -> i32 {
+    p.status
+        .as_ref()
+        .and_then(|s| s.container_statuses.as_ref())
+        .and_then(|cs| cs.iter().find(|c| c.name == container))
+        .map(|c| c.restart_count)
+        .unwrap_or(0)
+}
+
+// Prints the last lines of the container's previous instance between two markers.
+// Returns false once the stream loop has gone away.
+async fn replay_previous(
+    pods: &Api<Pod>,
+    retry: &RetryPolicy,
+    pod: &PodOption,
+    container: &str,
+    tail: Option<i64>,
+    label: &str,
+    tx: &Sender<StreamEvent>,
+) -> bool {
+    let lp = LogParams {
+        previous: true,
+        tail_lines: tail,
+        container: Some(container.to_string()),
+        ..LogParams::default()
+    };
+
+    let logs = match retry.call(None, || pods.logs(&pod.name, &lp)).await {
+        Ok(logs) => logs,
+        // Not worth ending the stream over, but it shouldn't pass for a plain marker either
+        Err(e) => {
+            let error = StreamError {
+                cluster: pod.cluster.clone(),
+                namespace: pod.namespace.clone(),
+                pod_name: pod.name.clone(),
+                container_name: Some(container.to_string()),
+                error: KlogError::Other(format!("could not fetch {}: {}", label, KlogError::from(e))),
+                fatal: false,
+            };
+            return tx.send(StreamEvent::Error(error)).await.is_ok();
+        }
+    };
+
+    let lines: Vec<&str> = logs.lines().collect();
+    if tx.send(marker(pod, container, format!("▼ {} · last {} lines", label, lines.len()))).await.is_err() {
+        return false;
+    }
+    for line in lines {
+        let msg = LogMessage {
+            cluster: pod.cluster.clone(),
+            namespace: pod.namespace.clone(),
+            pod_name: pod.name.clone(),
+            container_name: container.to_string(),
+            message: line.to_string(),
+        };
+        if tx.send(StreamEvent::Replay(msg)).await.is_err() {
+            return false;
+        }
+    }
+    tx.send(marker(pod, container, format!("▲ end of {}", label))).await.is_ok()
+}
+
+fn marker(pod: &PodOption, container: &str, text: String) -> StreamEvent {
+    StreamEvent::Marker(Marker {
+        cluster: pod.cluster.clone(),
+        namespace: pod.namespace.clone(),
+        pod_name: pod.name.clone(),
+        container_name: Some(container.to_string()),
+        text,
+    })
+}
+
+// "2024-05-01T12:00:00.123456789Z message" -> (timestamp, "message")
+fn split_timestamp(line: &str) -> Option<(Timestamp, &str)> {
+    let (ts, rest) = line.split_once(' ').unwrap_or((line, ""));
+    ts.parse::<Timestamp>().ok().map(|ts| (ts, rest))
+}
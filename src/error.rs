@@ -0,0 +1,276 @@
+use colored::*;
+use regex::Regex;
+use std::fmt;
+
+/// Everything klog can fail with, each with a hint on what to do about it.
+///
+/// Commands still bubble errors up as `Box<dyn Error>` so `?` keeps working on
+/// kube, inquire and io errors; `KlogError::from` classifies them once, where
+/// they are shown.
+#[derive(Clone, Debug)]
+pub enum KlogError {
+    /// No usable kubeconfig (or in-cluster config) was found, or the chosen context isn't in it
+    KubeconfigMissing(String),
+    /// The API server rejected our credentials (401, failed exec/OIDC plugin)
+    AuthExpired(String),
+    /// RBAC denied a request
+    Forbidden { verb: String, resource: String, namespace: Option<String> },
+    NotFound { kind: String, name: String, message: String },
+    InvalidPattern { flag: String, pattern: String, reason: String },
+    /// A flag value that isn't a pattern but still can't be used
+    InvalidArgument(String),
+    /// The API server didn't answer in time
+    Timeout(String),
+    /// DNS or TCP failure before the API server could answer
+    Unreachable(String),
+    /// The TLS handshake failed, usually an untrusted certificate
+    Tls(String),
+    /// The user backed out of a prompt
+    Cancelled,
+    /// Any other API error, with its HTTP status code
+    Api { code: u16, message: String },
+    Other(String),
+}
+
+impl KlogError {
+    pub fn hint(&self) -> Option<String> {
+        match self {
+            KlogError::KubeconfigMissing(_) => {
+                Some("pass --kubeconfig or set KUBECONFIG, and check --context against `kubectl config get-contexts`".to_string())
+            }
+            KlogError::AuthExpired(_) => Some(
+                "refresh your SSO token (re-run your cloud/OIDC login), then retry".to_string(),
+            ),
+            KlogError::Forbidden { verb, resource, namespace, .. } => {
+                let scope = match namespace {
+                    Some(ns) => format!("-n {}", ns),
+                    None => "-A".to_string(),
+                };
+                let check = format!("check with `kubectl auth can-i {} {} {}`", verb, resource, scope);
+                if resource == "namespaces" {
+                    return Some(format!("{}, or list the namespaces you can use in ${}", check, crate::utils::FALLBACK_NAMESPACES_ENV));
+                }
+                Some(check)
+            }
+            KlogError::NotFound { kind, name, .. } => {
+                Some(format!("check the name and namespace with `kubectl get {} {}`", kind, name))
+            }
+            KlogError::InvalidPattern { .. } => Some(
+                "patterns use Rust regex syntax; escape special characters, e.g. `\\.` or `\\(`".to_string(),
+            ),
+            KlogError::Timeout(_) => Some("the API server is slow or overloaded; retry in a moment".to_string()),
+            KlogError::Tls(_) => Some(
+                "if a proxy inspects TLS, trust its root CA with --ca-file; check --tls-server-name".to_string(),
+            ),
+            KlogError::Unreachable(_) => {
+                Some("check your VPN/proxy and that the cluster address in kubeconfig is reachable".to_string())
+            }
+            KlogError::InvalidArgument(_) | KlogError::Cancelled | KlogError::Api { .. } | KlogError::Other(_) => None,
+        }
+    }
+
+    /// Classifies an API error status. 403 messages name the verb and resource, e.g.
+    /// `pods "x" is forbidden: User "u" cannot get resource "pods/log" in API group "" in the namespace "ns"`
+    pub fn from_status(status: &kube::core::Status) -> KlogError {
+        let message = status.message.clone();
+        match status.code {
+            401 => KlogError::AuthExpired(message),
+            403 => {
+                let re = Regex::new(r#"cannot (\S+) resource "([^"]+)"(?: in API group "[^"]*")?(?: in the namespace "([^"]+)")?"#)
+                    .expect("static regex");
+                match re.captures(&message) {
+                    Some(c) => KlogError::Forbidden {
+                        verb: c[1].to_string(),
+                        resource: c[2].to_string(),
+                        namespace: c.get(3).map(|m| m.as_str().to_string()),
+                    },
+                    None => KlogError::Forbidden {
+                        verb: "access".to_string(),
+                        resource: status.details.as_ref().map(|d| d.kind.clone()).unwrap_or_default(),
+                        namespace: None,
+                    },
+                }
+            }
+            404 => {
+                let details = status.details.as_ref();
+                KlogError::NotFound {
+                    kind: details.map(|d| d.kind.clone()).unwrap_or_else(|| "pods".to_string()),
+                    name: details.map(|d| d.name.clone()).unwrap_or_default(),
+                    message,
+                }
+            }
+            408 | 504 => KlogError::Timeout(message),
+            code => KlogError::Api { code, message },
+        }
+    }
+
+    /// Failures a fresh client might fix: expired credentials or a dropped connection.
+    pub fn is_connection_failure(&self) -> bool {
+        matches!(self, KlogError::AuthExpired(_) | KlogError::Unreachable(_) | KlogError::Timeout(_))
+    }
+
+    /// Process exit code in one-shot mode. 2 matches clap's usage errors.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            KlogError::Other(_) | KlogError::Api { .. } => 1,
+            KlogError::InvalidPattern { .. } | KlogError::InvalidArgument(_) => 2,
+            KlogError::KubeconfigMissing(_) => 3,
+            KlogError::AuthExpired(_) => 4,
+            KlogError::Forbidden { .. } => 5,
+            KlogError::NotFound { .. } => 6,
+            KlogError::Timeout(_) | KlogError::Unreachable(_) | KlogError::Tls(_) => 7,
+            KlogError::Cancelled => 130,
+        }
+    }
+
+    /// Prints the error and its hint the same way everywhere (shell and one-shot).
+    pub fn render(&self) {
+        if let KlogError::Cancelled = self {
+            eprintln!("{}", "Cancelled.".dimmed());
+            return;
+        }
+        eprintln!("{} {}", "❌".red(), self.to_string().red().bold());
+        if let Some(hint) = self.hint() {
+            eprintln!("   {} {}", "hint:".yellow().bold(), hint);
+        }
+    }
+}
+
+impl fmt::Display for KlogError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KlogError::KubeconfigMissing(detail) => write!(f, "cannot load kubeconfig: {}", detail),
+            KlogError::AuthExpired(detail) => write!(f, "authentication failed: {}", detail),
+            KlogError::Forbidden { verb, resource, namespace, .. } => match namespace {
+                Some(ns) => write!(f, "forbidden: cannot {} {} in namespace {}", verb, resource, ns),
+                None => write!(f, "forbidden: cannot {} {}", verb, resource),
+            },
+            KlogError::NotFound { message, .. } => write!(f, "not found: {}", message),
+            KlogError::InvalidPattern { flag, pattern, reason } => {
+                write!(f, "invalid {} pattern '{}'\n{}", flag, pattern, reason)
+            }
+            KlogError::InvalidArgument(detail) => write!(f, "{}", detail),
+            KlogError::Timeout(detail) => write!(f, "API request timed out: {}", detail),
+            KlogError::Unreachable(detail) => write!(f, "cannot reach the API server: {}", detail),
+            KlogError::Tls(detail) => write!(f, "TLS failure: {}", detail),
+            KlogError::Cancelled => write!(f, "cancelled"),
+            KlogError::Api { code, message } => write!(f, "API error {}: {}", code, message),
+            KlogError::Other(detail) => write!(f, "{}", detail),
+        }
+    }
+}
+
+impl std::error::Error for KlogError {}
+
+/// An error from a command that already did its work (streamed logs, printed a
+/// diagnosis). Shown like the error it wraps, but the shell won't reconnect and
+/// run the command again over it.
+#[derive(Debug)]
+pub struct NotRetryable(pub KlogError);
+
+impl fmt::Display for NotRetryable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl std::error::Error for NotRetryable {}
+
+impl From<kube::Error> for KlogError {
+    fn from(e: kube::Error) -> Self {
+        match e {
+            kube::Error::Api(status) => KlogError::from_status(&status),
+            kube::Error::Auth(e) => KlogError::AuthExpired(e.to_string()),
+            kube::Error::InferConfig(e) => KlogError::KubeconfigMissing(source_chain(&e)),
+            kube::Error::InferKubeconfig(e) => KlogError::KubeconfigMissing(source_chain(&e)),
+            other => {
+                let detail = source_chain(&other);
+                if is_timeout(&other) {
+                    KlogError::Timeout(detail)
+                } else if detail.to_lowercase().contains("certificate") {
+                    KlogError::Tls(detail)
+                } else if matches!(other, kube::Error::HyperError(_) | kube::Error::Service(_)) {
+                    KlogError::Unreachable(detail)
+                } else {
+                    KlogError::Other(detail)
+                }
+            }
+        }
+    }
+}
+
+impl From<kube::config::KubeconfigError> for KlogError {
+    fn from(e: kube::config::KubeconfigError) -> Self {
+        KlogError::KubeconfigMissing(source_chain(&e))
+    }
+}
+
+impl From<kube::config::InferConfigError> for KlogError {
+    fn from(e: kube::config::InferConfigError) -> Self {
+        KlogError::KubeconfigMissing(source_chain(&e))
+    }
+}
+
+impl From<Box<dyn std::error::Error + Send + Sync>> for KlogError {
+    fn from(e: Box<dyn std::error::Error + Send + Sync>) -> Self {
+        let e = match e.downcast::<KlogError>() {
+            Ok(e) => return *e,
+            Err(e) => e,
+        };
+        let e = match e.downcast::<NotRetryable>() {
+            Ok(e) => return e.0,
+            Err(e) => e,
+        };
+        let e = match e.downcast::<kube::Error>() {
+            Ok(e) => return KlogError::from(*e),
+            Err(e) => e,
+        };
+        if let Some(e) = e.downcast_ref::<kube::config::InferConfigError>() {
+            return KlogError::KubeconfigMissing(source_chain(e));
+        }
+        if let Some(e) = e.downcast_ref::<kube::config::KubeconfigError>() {
+            return KlogError::KubeconfigMissing(source_chain(e));
+        }
+        if let Some(e) = e.downcast_ref::<inquire::InquireError>()
+            && matches!(e, inquire::InquireError::OperationCanceled | inquire::InquireError::OperationInterrupted)
+        {
+            return KlogError::Cancelled;
+        }
+        if e.is::<tokio::time::error::Elapsed>() {
+            return KlogError::Timeout(e.to_string());
+        }
+        KlogError::Other(e.to_string())
+    }
+}
+
+fn source_chain(e: &dyn std::error::Error) -> String {
+    let mut text = e.to_string();
+    let mut source = e.source();
+    while let Some(s) = source {
+        // Some errors already fold their sources into their own message
+        let next = s.to_string();
+        if !text.contains(&next) {
+            text.push_str(": ");
+            text.push_str(&next);
+        }
+        source = s.source();
+    }
+    text
+}
+
+fn is_timeout(e: &(dyn std::error::Error + 'static)) -> bool {
+    let mut current = Some(e);
+    while let Some(e) = current {
+        if let Some(io) = e.downcast_ref::<std::io::Error>()
+            && io.kind() == std::io::ErrorKind::TimedOut
+        {
+            return true;
+        }
+        let text = e.to_string().to_lowercase();
+        if text.contains("timed out") || text.contains("deadline has elapsed") {
+            return true;
+        }
+        current = e.source();
+    }
+    false
+}
@@ -0,0 +1,63 @@
+use crate::error::KlogError;
+use std::future::Future;
+use std::sync::OnceLock;
+use tokio::sync::broadcast;
+
+// Signal handlers are process-wide and can't be removed once installed, so
+// there is exactly one listener and everything else subscribes to it.
+static SIGNALS: OnceLock<broadcast::Sender<()>> = OnceLock::new();
+
+/// Starts the single SIGINT/SIGTERM listener. Call once, early in `main`.
+pub fn listen() {
+    let tx = SIGNALS.get_or_init(|| broadcast::channel(1).0).clone();
+    tokio::spawn(async move {
+        loop {
+            next_signal().await;
+            let _ = tx.send(());
+        }
+    });
+}
+
+/// Resolves on the next SIGINT or SIGTERM after the call.
+pub async fn interrupted() {
+    match SIGNALS.get() {
+        Some(tx) => {
+            let _ = tx.subscribe().recv().await;
+        }
+        // Nobody is listening, so nothing will ever arrive
+        None => std::future::pending().await,
+    }
+}
+
+/// Runs `fut` until it finishes or a signal arrives, in which case it is
+/// dropped and the result is `Cancelled`. The shell stays alive either way.
+pub async fn or_cancel<T, E: From<KlogError>>(fut: impl Future<Output = Result<T, E>>) -> Result<T, E> {
+    tokio::select! {
+        // Polled first, so a stream loop that saw the same signal can still wind down cleanly
+        biased;
+        result = fut => result,
+        _ = interrupted() => Err(KlogError::Cancelled.into()),
+    }
+}
+
+async fn next_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+        match signal(SignalKind::terminate()) {
+            Ok(mut term) => {
+                tokio::select! {
+                    _ = tokio::signal::ctrl_c() => {}
+                    _ = term.recv() => {}
+                }
+            }
+            Err(_) => {
+                let _ = tokio::signal::ctrl_c().await;
+            }
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = tokio::signal::ctrl_c().await;
+    }
+}
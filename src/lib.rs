@@ -0,0 +1,8 @@
+pub mod commands;
+pub mod error;
+pub mod interrupt;
+pub mod models;
+pub mod rbac;
+pub mod retry;
+pub mod session;
+pub mod utils;
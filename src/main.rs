@@ -1,16 +1,16 @@
-mod commands;
-mod models;
-pub mod utils;
-
 use clap::{Parser, Subcommand, CommandFactory};
 use colored::*;
-use kube::Client;
+use klog::error::{KlogError, NotRetryable};
+use klog::session::{ConnectionArgs, Session};
+use klog::{commands, interrupt, utils};
 use rustyline::error::ReadlineError;
 use rustyline::DefaultEditor; // The engine for history and arrows
 
 #[derive(Parser)]
 #[command(name = "klog", author, version, about)]
 struct Cli {
+    #[command(flatten)]
+    connection: ConnectionArgs,
     #[command(subcommand)]
     command: Option<Commands>,
 }
@@ -18,84 +18,130 @@ struct Cli {
 #[derive(Subcommand, Clone, Debug)]
 pub enum Commands {
     /// Tail logs from pods
-    Log {
-        /// If specified, fetch logs from this pod only
-        pod: Option<String>,
-        #[arg(short, long, num_args = 0..=1, default_missing_value = None)]
-        /// If specified, fetch logs from pods in this deployment only
-        deployment: Option<Option<String>>,
-        /// If specified, list pods from this namespace only. If pass -n only then list namespaces to choose from. IF not specified, use current context namespace.
-        #[arg(short, long, num_args = 0..=1, default_missing_value = None)]
-        namespace: Option<Option<String>>,
-        /// If specified, prompt to select containers within pods
-        #[arg(short, long, default_value_t = false)]
-        container_select: bool,
-        /// Filter logs by this string (regex supported)
-        #[arg(short, long)]
-        filter: Option<String>,
-        /// Exclude logs by this string (regex supported)
-        #[arg(short, long)]
-        exclude: Option<String>,
-        /// If specified, fetch previous logs
-        #[arg(short, long, default_value_t = false)]
-        previous: bool,
-        /// Number of lines from the end of the logs to show. * for all
-        #[arg(short, long, default_value = "50")]
-        tail: String,
-    },
+    Log(commands::log::LogArgs),
     /// Summarized diagnostic of a pod's health
     Describe {
         #[arg(short, long)]
         pod: Option<String>,
         #[arg(short, long, num_args = 0..=1, default_missing_value = None)]
         namespace: Option<Option<String>>,
+        /// How many of the most recent events to show
+        #[arg(long, value_name = "N", default_value_t = 5)]
+        events: usize,
+        /// Only show Warning events
+        #[arg(long, default_value_t = false)]
+        warnings_only: bool,
     },
+    /// Check each step of reaching the API server (config, DNS, TCP, proxy, TLS, auth)
+    Doctor,
 }
 
 #[tokio::main]
-async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    inquire::set_global_render_config(crate::utils::get_transparent_theme());
+async fn main() {
+    inquire::set_global_render_config(utils::get_transparent_theme());
     rustls::crypto::ring::default_provider().install_default().ok();
 
-    // 2. Initial connection (Zscaler tax paid here once)
-    let pb = crate::utils::create_spinner("Connecting to Kubernetes...");
-    let client = match Client::try_default().await {
-        Ok(c) => c,
-        Err(e) => {
-            pb.finish_and_clear();
-            eprintln!("❌ Connection Error: {e}");
-            return Ok(());
+    // 1. `klog <command> ...` runs once and exits; bare `klog` opens the shell
+    let cli = Cli::parse();
+    // Ctrl-C / SIGTERM cancel the running command (and end the process in one-shot mode)
+    interrupt::listen();
+
+    // The doctor diagnoses the connection, so it must not need one
+    if let Some(Commands::Doctor) = cli.command {
+        if let Err(e) = interrupt::or_cancel(commands::doctor::run(&cli.connection)).await {
+            fail(KlogError::from(e));
         }
+        return;
+    }
+
+    // 2. Initial connection (Zscaler tax paid here once)
+    let session = match connect(cli.connection).await {
+        Ok(s) => s,
+        Err(e) => fail(e),
+    };
+
+    let result = match cli.command {
+        Some(cmd) => execute(&session, cmd).await,
+        // 3. Enter the Shell
+        None => run_shell(session).await,
     };
+    if let Err(e) = result {
+        fail(KlogError::from(e));
+    }
+}
+
+async fn connect(connection: ConnectionArgs) -> Result<Session, KlogError> {
+    let pb = utils::create_spinner("Connecting to Kubernetes...");
+    let session = interrupt::or_cancel(Session::connect(connection)).await;
     pb.finish_and_clear();
+    session
+}
 
-    // 3. Enter the Shell
-    run_shell(client).await?;
+fn fail(e: KlogError) -> ! {
+    e.render();
+    std::process::exit(e.exit_code());
+}
 
-    Ok(())
+async fn execute(session: &Session, cmd: Commands) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    interrupt::or_cancel(run_command(session, cmd)).await
 }
 
-async fn execute(client: Client, cmd: Commands) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+async fn run_command(session: &Session, cmd: Commands) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     match cmd {
-        Commands::Log { pod, deployment, namespace, container_select, filter, exclude, previous, tail } => {
-            commands::log::run(client, pod, deployment, namespace, container_select, filter, exclude, previous, tail).await
+        Commands::Log(args) => {
+            commands::log::run(session, args).await
+        }
+        Commands::Describe { pod, namespace, events, warnings_only } => {
+            commands::describe::run(session, pod, namespace, events, warnings_only).await
         }
-        Commands::Describe { pod, namespace } => {
-            commands::describe::run(client, pod, namespace).await
+        // The failed check is the diagnosis; running it again adds nothing
+        Commands::Doctor => commands::doctor::run(&session.connection)
+            .await
+            .map_err(|e| NotRetryable(KlogError::from(e)).into()),
+    }
+}
+
+// Shell sessions outlive tokens: on an auth or connection failure the client is
+// rebuilt (re-reading the kubeconfig and re-running exec/OIDC plugins) and the
+// command retried once, unless it failed after doing its work.
+async fn execute_with_reconnect(session: &mut Session, cmd: Commands) -> Result<(), KlogError> {
+    let (err, retryable) = match execute(session, cmd.clone()).await {
+        Ok(()) => return Ok(()),
+        Err(e) => {
+            let retryable = !e.is::<NotRetryable>();
+            (KlogError::from(e), retryable)
         }
+    };
+    if !retryable || !err.is_connection_failure() {
+        return Err(err);
     }
+
+    let pb = utils::create_spinner(&format!("{} Reconnecting to {}...", err.to_string().dimmed(), session.context));
+    let reconnected = Session::connect(session.connection.clone()).await;
+    pb.finish_and_clear();
+    let mut fresh = reconnected?;
+    // Keep what `ns` set for this session
+    fresh.namespace = session.namespace.clone();
+    *session = fresh;
+
+    execute(session, cmd).await.map_err(KlogError::from)
 }
 
-async fn run_shell(client: Client) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+async fn run_shell(mut session: Session) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     println!("{}", "\n--- 🐚 klog interactive shell ---".bright_white().bold());
-    println!("Commands: 'log', 'describe', 'help', 'exit'. Up/Down for history.");
+    println!("Context: {} · namespace: {}", session.context.cyan().bold(), session.namespace.cyan());
+    if let Some(identity) = session.connection.impersonation() {
+        println!("Impersonating: {}", identity.magenta().bold());
+    }
+    println!("Commands: 'log', 'describe', 'ctx', 'ns', 'help', 'exit'. Up/Down for history.");
 
     // Initialize the history editor
     let mut rl = DefaultEditor::new()?;
     
     loop {
         // PROMPT: This replaces inquire::Text
-        let readline = rl.readline("klog> ");
+        let identity = session.connection.as_user.as_ref().map(|u| format!(" as {}", u)).unwrap_or_default();
+        let readline = rl.readline(&format!("klog [{}/{}{}]> ", session.context, session.namespace, identity));
 
         match readline {
             Ok(line) => {
@@ -109,19 +155,42 @@ async fn run_shell(client: Client) -> Result<(), Box<dyn std::error::Error + Sen
                 if line == "help" {
                     let mut cmd = Cli::command();
                     let _ = cmd.print_help();
+                    println!("\nShell commands:");
+                    println!("  ctx [NAME]    Switch kube context (pick from the kubeconfig without NAME)");
+                    println!("  ns [NAME|-]   Set the namespace used when -n is omitted (- resets to the context's)");
                     println!();
                     continue;
                 }
 
                 if let Some(mut parts) = shlex::split(line) {
+                    if let Some(result) = run_builtin(&mut session, &parts).await {
+                        if let Err(e) = result {
+                            KlogError::from(e).render();
+                        }
+                        continue;
+                    }
                     parts.insert(0, "klog".to_string());
 
                     match Cli::try_parse_from(parts) {
                         Ok(cli) => {
                             if let Some(cmd) = cli.command {
-                                // We print errors if the command fails
-                                if let Err(e) = execute(client.clone(), cmd).await {
-                                    eprintln!("{} {}", "Error:".red(), e);
+                                // --context etc. on a single line apply to that command only, on top
+                                // of the shell's own connection and its `ns` namespace
+                                let mut one_off = if cli.connection.is_set() {
+                                    match connect(cli.connection.over(&session.connection)).await {
+                                        Ok(s) => Some(Session { namespace: session.namespace.clone(), ..s }),
+                                        Err(e) => {
+                                            e.render();
+                                            continue;
+                                        }
+                                    }
+                                } else {
+                                    None
+                                };
+                                // Errors are shown with their hint; the shell keeps going
+                                let target = one_off.as_mut().unwrap_or(&mut session);
+                                if let Err(e) = execute_with_reconnect(target, cmd).await {
+                                    e.render();
                                 }
                             }
                         }
@@ -139,4 +208,52 @@ async fn run_shell(client: Client) -> Result<(), Box<dyn std::error::Error + Sen
     }
     
     Ok(())
-}
\ No newline at end of file
+}
+
+// --- SHELL BUILTINS ---
+// `ctx` and `ns` change what later commands talk to, so they only exist in the shell.
+// Returns None when the line isn't a builtin.
+async fn run_builtin(
+    session: &mut Session,
+    parts: &[String],
+) -> Option<Result<(), Box<dyn std::error::Error + Send + Sync>>> {
+    let (name, rest) = parts.split_first()?;
+    let arg = rest.first().cloned();
+    match name.as_str() {
+        "ctx" => Some(interrupt::or_cancel(switch_context(session, arg)).await),
+        "ns" => Some(interrupt::or_cancel(set_namespace(session, arg)).await),
+        _ => None,
+    }
+}
+
+// `ctx` picks from the kubeconfig, `ctx NAME` switches directly
+async fn switch_context(session: &mut Session, name: Option<String>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let name = match name {
+        Some(name) => name,
+        None => {
+            let contexts = session.connection.contexts().map_err(KlogError::from)?;
+            let current = contexts.iter().position(|c| *c == session.context).unwrap_or(0);
+            inquire::Select::new("Switch to context:", contexts).with_starting_cursor(current).prompt()?
+        }
+    };
+    // --cluster/--user overrides belonged to the old context
+    let connection = ConnectionArgs { context: Some(name), cluster: None, user: None, ..session.connection.clone() };
+    *session = connect(connection).await?;
+    println!("Switched to context {} (namespace {})", session.context.cyan().bold(), session.namespace.cyan());
+    Ok(())
+}
+
+// `ns` picks, `ns NAME` sets, `ns -` goes back to the context's namespace
+async fn set_namespace(session: &mut Session, name: Option<String>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    session.namespace = match name.as_deref() {
+        Some("-") => session.context_namespace.clone(),
+        Some(ns) => ns.to_string(),
+        None => {
+            let namespaces = utils::list_namespaces(session).await?;
+            let current = namespaces.iter().position(|n| *n == session.namespace).unwrap_or(0);
+            inquire::Select::new("Default namespace:", namespaces).with_starting_cursor(current).prompt()?
+        }
+    };
+    println!("Commands without -n now use namespace {}", session.namespace.cyan());
+    Ok(())
+}
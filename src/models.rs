@@ -1,3 +1,4 @@
+use crate::error::KlogError;
 use std::fmt;
 
 #[derive(Clone)]
@@ -5,23 +6,126 @@ pub struct PodOption {
     pub name: String,
     pub namespace: String,
     pub containers: Vec<String>,
+    /// Kube context the pod lives in; only set when tailing several clusters
+    pub cluster: Option<String>,
 }
 
 impl fmt::Display for PodOption {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{} ({})", self.name, self.namespace)
+        match &self.cluster {
+            Some(cluster) => write!(f, "{} ({} @ {})", self.name, self.namespace, cluster),
+            None => write!(f, "{} ({})", self.name, self.namespace),
+        }
     }
 }
 
 impl fmt::Display for LogMessage {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "[{}/{}] {}", self.pod_name, self.container_name, self.message)
+        write!(f, "[{}] {}", self.stream(), self.message)
     }
 }
 
 #[derive(Clone)]
 pub struct LogMessage {
+    pub cluster: Option<String>,
+    pub namespace: String,
     pub pod_name: String,
     pub container_name: String,
     pub message: String,
-}
\ No newline at end of file
+}
+
+impl LogMessage {
+    pub fn stream(&self) -> String {
+        stream_label(self.cluster.as_deref(), &self.namespace, &self.pod_name, &self.container_name)
+    }
+}
+
+/// "namespace/pod/container", prefixed with "cluster/" when tailing several clusters.
+/// Also the key of a stream's pane and stats, so same-named pods never share one.
+pub fn stream_label(cluster: Option<&str>, namespace: &str, pod: &str, container: &str) -> String {
+    format!("{}/{}", pod_label(cluster, namespace, pod), container)
+}
+
+/// "namespace/pod", prefixed with "cluster/" when tailing several clusters.
+pub fn pod_label(cluster: Option<&str>, namespace: &str, pod: &str) -> String {
+    match cluster {
+        Some(cluster) => format!("{}/{}/{}", cluster, namespace, pod),
+        None => format!("{}/{}", namespace, pod),
+    }
+}
+
+/// What log workers send to the stream loop.
+pub enum StreamEvent {
+    Line(LogMessage),
+    /// A line from a terminated container instance, replayed for context
+    Replay(LogMessage),
+    Kube(PodEvent),
+    Marker(Marker),
+    Error(StreamError),
+    Ended { cluster: Option<String>, namespace: String, pod_name: String, container_name: String, reason: String },
+}
+
+/// A synthetic line about a pod or container's lifecycle, injected into the stream.
+/// Without a container it applies to every stream of the pod.
+#[derive(Clone)]
+pub struct Marker {
+    pub cluster: Option<String>,
+    pub namespace: String,
+    pub pod_name: String,
+    pub container_name: Option<String>,
+    pub text: String,
+}
+
+/// A Kubernetes `Event` about one of the tailed pods, as shown next to the logs.
+#[derive(Clone)]
+pub struct PodEvent {
+    pub uid: String,
+    pub cluster: Option<String>,
+    pub namespace: String,
+    pub pod_name: String,
+    pub warning: bool,
+    pub reason: String,
+    pub message: String,
+    pub count: i32,
+    pub time: String,
+}
+
+/// A problem with one stream. Fatal errors end that stream; the rest are warnings.
+/// Without a container it is about the whole pod (e.g. its events watch).
+#[derive(Clone)]
+pub struct StreamError {
+    pub cluster: Option<String>,
+    pub namespace: String,
+    pub pod_name: String,
+    pub container_name: Option<String>,
+    /// Classified like any other klog error, so RBAC problems read the same everywhere
+    pub error: KlogError,
+    pub fatal: bool,
+}
+
+impl Marker {
+    /// The stream label, or the pod label for pod-wide markers.
+    pub fn stream(&self) -> String {
+        match &self.container_name {
+            Some(c) => stream_label(self.cluster.as_deref(), &self.namespace, &self.pod_name, c),
+            None => pod_label(self.cluster.as_deref(), &self.namespace, &self.pod_name),
+        }
+    }
+}
+
+impl PodEvent {
+    /// "namespace/pod", or "cluster/namespace/pod" when tailing several clusters.
+    pub fn pod(&self) -> String {
+        pod_label(self.cluster.as_deref(), &self.namespace, &self.pod_name)
+    }
+}
+
+impl StreamError {
+    /// The stream label, or the pod label for pod-wide errors.
+    pub fn stream(&self) -> String {
+        match &self.container_name {
+            Some(c) => stream_label(self.cluster.as_deref(), &self.namespace, &self.pod_name, c),
+            None => pod_label(self.cluster.as_deref(), &self.namespace, &self.pod_name),
+        }
+    }
+}
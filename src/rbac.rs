@@ -0,0 +1,82 @@
+use crate::error::KlogError;
+use futures::future::join_all;
+use k8s_openapi::api::authorization::v1::{ResourceAttributes, SelfSubjectAccessReview, SelfSubjectAccessReviewSpec};
+use crate::session::Session;
+use kube::{Api, api::PostParams};
+use std::fmt;
+
+/// One RBAC permission, written the kubectl way: `pods/log` for a subresource,
+/// `deployments.apps` for a resource outside the core group.
+#[derive(Clone, Debug)]
+pub struct Permission {
+    pub verb: &'static str,
+    pub resource: &'static str,
+    pub namespace: Option<String>,
+}
+
+impl Permission {
+    pub fn new(verb: &'static str, resource: &'static str, namespace: Option<&str>) -> Self {
+        Self { verb, resource, namespace: namespace.map(str::to_string) }
+    }
+
+    fn attributes(&self) -> ResourceAttributes {
+        let (resource, subresource) = match self.resource.split_once('/') {
+            Some((r, s)) => (r, Some(s.to_string())),
+            None => (self.resource, None),
+        };
+        let (resource, group) = resource.split_once('.').unwrap_or((resource, ""));
+        ResourceAttributes {
+            verb: Some(self.verb.to_string()),
+            resource: Some(resource.to_string()),
+            group: Some(group.to_string()),
+            subresource,
+            namespace: self.namespace.clone(),
+            ..ResourceAttributes::default()
+        }
+    }
+}
+
+impl fmt::Display for Permission {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.resource, self.verb)
+    }
+}
+
+// --- ACCESS REVIEWS ---
+/// Returns the permissions the current user lacks. Reviews run in parallel; one
+/// that can't be evaluated counts as allowed, so the real call decides.
+pub async fn missing(session: &Session, perms: &[Permission]) -> Vec<Permission> {
+    let api: Api<SelfSubjectAccessReview> = Api::all(session.client.clone());
+    let reviews = perms.iter().map(|perm| {
+        let api = api.clone();
+        async move {
+            let review = SelfSubjectAccessReview {
+                spec: SelfSubjectAccessReviewSpec {
+                    resource_attributes: Some(perm.attributes()),
+                    ..SelfSubjectAccessReviewSpec::default()
+                },
+                ..SelfSubjectAccessReview::default()
+            };
+            let pp = PostParams::default();
+            match session.retry.call(None, || api.create(&pp, &review)).await {
+                Ok(r) => r.status.is_some_and(|s| !s.allowed),
+                Err(_) => false,
+            }
+        }
+    });
+    let denied = join_all(reviews).await;
+    perms.iter().zip(denied).filter(|(_, d)| *d).map(|(p, _)| p.clone()).collect()
+}
+
+/// Preflight for an operation: fails with the first missing permission, before
+/// any prompt or stream is started.
+pub async fn require(session: &Session, perms: &[Permission]) -> Result<(), KlogError> {
+    match missing(session, perms).await.into_iter().next() {
+        Some(perm) => Err(KlogError::Forbidden {
+            verb: perm.verb.to_string(),
+            resource: perm.resource.to_string(),
+            namespace: perm.namespace,
+        }),
+        None => Ok(()),
+    }
+}
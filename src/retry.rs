@@ -0,0 +1,101 @@
+use crate::session::NetworkArgs;
+use indicatif::ProgressBar;
+use std::future::Future;
+use std::time::Duration;
+
+const DEFAULT_RETRIES: u32 = 3;
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+const BASE_DELAY: Duration = Duration::from_millis(250);
+const MAX_DELAY: Duration = Duration::from_secs(8);
+// A Retry-After longer than this isn't worth waiting for interactively
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(30);
+
+/// How list/get calls are retried; each session carries its own, built from
+/// the flags it was connected with.
+#[derive(Clone, Copy, Debug)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt
+    pub retries: u32,
+    /// Limit for a single attempt; None waits as long as the client does
+    pub timeout: Option<Duration>,
+}
+
+impl RetryPolicy {
+    pub fn from_args(args: &NetworkArgs) -> Self {
+        let timeout = match args.request_timeout {
+            Some(0) => None,
+            Some(secs) => Some(Duration::from_secs(secs)),
+            None => Some(DEFAULT_TIMEOUT),
+        };
+        Self { retries: args.retries.unwrap_or(DEFAULT_RETRIES), timeout }
+    }
+
+    /// Runs one API read, retrying throttling (429), server errors and timeouts with
+    /// exponential backoff and jitter. A Retry-After from the server wins over the
+    /// backoff. While retrying, the spinner shows its label with the attempt and why;
+    /// the label is passed in because concurrent calls may share one spinner.
+    pub async fn call<T, F, Fut>(&self, spinner: Option<(&ProgressBar, &str)>, mut op: F) -> Result<T, kube::Error>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, kube::Error>>,
+    {
+        let mut attempt = 0;
+        loop {
+            let result = match self.timeout {
+                Some(limit) => tokio::time::timeout(limit, op())
+                    .await
+                    .unwrap_or_else(|elapsed| Err(kube::Error::Service(Box::new(elapsed)))),
+                None => op().await,
+            };
+            let err = match result {
+                Ok(value) => {
+                    if let Some((pb, label)) = spinner {
+                        pb.set_message(label.to_string());
+                    }
+                    return Ok(value);
+                }
+                Err(e) => e,
+            };
+            let Some(reason) = retry_reason(&err) else {
+                return Err(err);
+            };
+            if attempt >= self.retries {
+                return Err(err);
+            }
+            attempt += 1;
+            if let Some((pb, label)) = spinner {
+                pb.set_message(format!("{} (retry {}/{}: {})", label, attempt, self.retries, reason));
+            }
+            tokio::time::sleep(retry_after(&err).unwrap_or_else(|| backoff(attempt))).await;
+        }
+    }
+}
+
+// Why `err` is worth another attempt, as shown on the spinner
+fn retry_reason(err: &kube::Error) -> Option<String> {
+    match err {
+        kube::Error::Api(status) => match status.code {
+            429 => Some("throttled".to_string()),
+            500 | 502 | 503 | 504 => Some(format!("server error {}", status.code)),
+            _ => None,
+        },
+        kube::Error::Service(e) if e.is::<tokio::time::error::Elapsed>() => Some("timed out".to_string()),
+        kube::Error::HyperError(_) | kube::Error::Service(_) => Some("connection error".to_string()),
+        _ => None,
+    }
+}
+
+fn retry_after(err: &kube::Error) -> Option<Duration> {
+    let kube::Error::Api(status) = err else {
+        return None;
+    };
+    let secs = status.details.as_ref()?.retry_after_seconds;
+    (secs > 0).then(|| Duration::from_secs(secs as u64).min(MAX_RETRY_AFTER))
+}
+
+// 250ms, 500ms, 1s... capped, then "equal jitter" so parallel callers spread out
+fn backoff(attempt: u32) -> Duration {
+    let full = BASE_DELAY.saturating_mul(1 << attempt.saturating_sub(1).min(16)).min(MAX_DELAY);
+    let half = full / 2;
+    half + half.mul_f64(fastrand::f64())
+}
@@ -0,0 +1,245 @@
+use crate::error::KlogError;
+use crate::retry::RetryPolicy;
+use clap::Args;
+use kube::{
+    Client, Config,
+    config::{KubeConfigOptions, Kubeconfig, KubeconfigError},
+};
+use std::path::PathBuf;
+use std::time::Duration;
+
+// Which kubeconfig, context, cluster and user to talk to. Unset fields follow kubectl's defaults.
+// (A plain comment: clap would otherwise use it as the program's about text.)
+#[derive(Args, Clone, Debug, Default, PartialEq)]
+pub struct ConnectionArgs {
+    /// Path to the kubeconfig file (default: $KUBECONFIG or ~/.kube/config)
+    #[arg(long, global = true, value_name = "PATH")]
+    pub kubeconfig: Option<PathBuf>,
+    /// Kubeconfig context to use (default: current-context)
+    #[arg(long, global = true)]
+    pub context: Option<String>,
+    /// Use this kubeconfig cluster instead of the context's
+    #[arg(long, global = true)]
+    pub cluster: Option<String>,
+    /// Use this kubeconfig user instead of the context's
+    #[arg(long, global = true)]
+    pub user: Option<String>,
+    /// Make every request as this user or service account (e.g. system:serviceaccount:NS:NAME)
+    #[arg(long = "as", global = true, value_name = "USER")]
+    pub as_user: Option<String>,
+    /// Group to impersonate along with --as; repeat or comma separate for several
+    #[arg(long = "as-group", global = true, value_name = "GROUP", value_delimiter = ',', requires = "as_user")]
+    pub as_groups: Vec<String>,
+    #[command(flatten)]
+    pub network: NetworkArgs,
+}
+
+// How to reach the API server from behind corporate proxies and TLS inspection.
+#[derive(Args, Clone, Debug, Default, PartialEq)]
+pub struct NetworkArgs {
+    /// Extra PEM CA bundle to trust on top of the cluster's CA (e.g. a TLS-inspection root)
+    #[arg(long, global = true, value_name = "PATH")]
+    pub ca_file: Option<PathBuf>,
+    /// HTTP proxy for API calls, tunnelled with CONNECT (default: $HTTPS_PROXY)
+    #[arg(long, global = true, value_name = "URL")]
+    pub proxy: Option<String>,
+    /// Comma separated hosts/domains that bypass the proxy (default: $NO_PROXY)
+    #[arg(long, global = true, value_name = "HOSTS")]
+    pub no_proxy: Option<String>,
+    /// Verify the API server certificate against this name instead of the URL's host
+    #[arg(long, global = true, value_name = "NAME")]
+    pub tls_server_name: Option<String>,
+    /// Seconds to wait for the TCP/TLS connection to the API server
+    #[arg(long, global = true, value_name = "SECS")]
+    pub connect_timeout: Option<u64>,
+    /// Seconds to wait for a response before giving up (idle log streams reconnect)
+    #[arg(long, global = true, value_name = "SECS")]
+    pub read_timeout: Option<u64>,
+    /// Seconds each list/get call may take before it is retried (default: 30, 0 waits forever)
+    #[arg(long, global = true, value_name = "SECS")]
+    pub request_timeout: Option<u64>,
+    /// How often a throttled, failing or timed out list/get call is retried (default: 3)
+    #[arg(long, global = true, value_name = "N")]
+    pub retries: Option<u32>,
+}
+
+impl NetworkArgs {
+    /// Field by field, these settings where set and `base`'s otherwise.
+    pub fn over(&self, base: &NetworkArgs) -> NetworkArgs {
+        NetworkArgs {
+            ca_file: self.ca_file.clone().or_else(|| base.ca_file.clone()),
+            proxy: self.proxy.clone().or_else(|| base.proxy.clone()),
+            no_proxy: self.no_proxy.clone().or_else(|| base.no_proxy.clone()),
+            tls_server_name: self.tls_server_name.clone().or_else(|| base.tls_server_name.clone()),
+            connect_timeout: self.connect_timeout.or(base.connect_timeout),
+            read_timeout: self.read_timeout.or(base.read_timeout),
+            request_timeout: self.request_timeout.or(base.request_timeout),
+            retries: self.retries.or(base.retries),
+        }
+    }
+
+    // The proxy that applies to `host` once NO_PROXY is taken into account
+    fn proxy_for(&self, host: &str) -> Option<String> {
+        let proxy = self.proxy.clone().or_else(|| env_any(&["HTTPS_PROXY", "https_proxy"]))?;
+        let no_proxy = self.no_proxy.clone().or_else(|| env_any(&["NO_PROXY", "no_proxy"])).unwrap_or_default();
+        (!bypasses_proxy(&no_proxy, host)).then_some(proxy)
+    }
+
+    fn apply(&self, config: &mut Config) -> Result<(), KlogError> {
+        if let Some(path) = &self.ca_file {
+            let extra = read_ca_file(path)?;
+            // An explicit root list replaces the system roots, so keep those when the cluster had no CA
+            let roots = config.root_cert.get_or_insert_with(|| {
+                rustls_native_certs::load_native_certs().certs.into_iter().map(|c| c.to_vec()).collect()
+            });
+            roots.extend(extra);
+        }
+
+        let host = config.cluster_url.host().unwrap_or_default().to_string();
+        if let Some(proxy) = self.proxy_for(&host) {
+            let url = proxy
+                .parse()
+                .map_err(|e| KlogError::InvalidArgument(format!("invalid proxy URL '{}': {}", proxy, e)))?;
+            config.proxy_url = Some(url);
+        }
+        if let Some(name) = &self.tls_server_name {
+            config.tls_server_name = Some(name.clone());
+        }
+        if let Some(secs) = self.connect_timeout {
+            config.connect_timeout = Some(Duration::from_secs(secs));
+        }
+        if let Some(secs) = self.read_timeout {
+            config.read_timeout = Some(Duration::from_secs(secs));
+        }
+        Ok(())
+    }
+}
+
+fn env_any(names: &[&str]) -> Option<String> {
+    names.iter().find_map(|n| std::env::var(n).ok()).filter(|v| !v.is_empty())
+}
+
+// NO_PROXY entries match the host itself or any subdomain; "*" matches everything
+fn bypasses_proxy(no_proxy: &str, host: &str) -> bool {
+    no_proxy.split(',').map(str::trim).filter(|e| !e.is_empty()).any(|entry| {
+        let entry = entry.split(':').next().unwrap_or(entry).trim_start_matches('.');
+        entry == "*" || host == entry || host.ends_with(&format!(".{}", entry))
+    })
+}
+
+fn read_ca_file(path: &PathBuf) -> Result<Vec<Vec<u8>>, KlogError> {
+    let invalid = |why: String| KlogError::InvalidArgument(format!("--ca-file {}: {}", path.display(), why));
+    let text = std::fs::read(path).map_err(|e| invalid(e.to_string()))?;
+    let certs: Vec<Vec<u8>> = pem::parse_many(&text)
+        .map_err(|e| invalid(e.to_string()))?
+        .into_iter()
+        .filter(|p| p.tag() == "CERTIFICATE")
+        .map(|p| p.into_contents())
+        .collect();
+    if certs.is_empty() {
+        return Err(invalid("no PEM certificates found".to_string()));
+    }
+    Ok(certs)
+}
+
+impl ConnectionArgs {
+    pub fn is_set(&self) -> bool {
+        *self != Self::default()
+    }
+
+    /// These flags layered over `base`: whatever is set here wins, the rest is
+    /// kept. A new context drops base's --cluster/--user, which belonged to the old one.
+    pub fn over(&self, base: &ConnectionArgs) -> ConnectionArgs {
+        let same_context = self.context.is_none() || self.context == base.context;
+        let keep = |line: &Option<String>, base: &Option<String>| line.clone().or_else(|| base.clone().filter(|_| same_context));
+        let (as_user, as_groups) = match &self.as_user {
+            Some(user) => (Some(user.clone()), self.as_groups.clone()),
+            None => (base.as_user.clone(), base.as_groups.clone()),
+        };
+        ConnectionArgs {
+            kubeconfig: self.kubeconfig.clone().or_else(|| base.kubeconfig.clone()),
+            context: self.context.clone().or_else(|| base.context.clone()),
+            cluster: keep(&self.cluster, &base.cluster),
+            user: keep(&self.user, &base.user),
+            as_user,
+            as_groups,
+            network: self.network.over(&base.network),
+        }
+    }
+
+    // True when the user picked a kubeconfig, context, cluster or user explicitly
+    fn selects_context(&self) -> bool {
+        self.kubeconfig.is_some() || self.context.is_some() || self.cluster.is_some() || self.user.is_some()
+    }
+
+    /// Builds the client config these flags describe. Returns it with the context name.
+    pub async fn config(&self) -> Result<(Config, String), KlogError> {
+        let options = KubeConfigOptions {
+            context: self.context.clone(),
+            cluster: self.cluster.clone(),
+            user: self.user.clone(),
+        };
+        let (mut config, context) = match self.read_kubeconfig() {
+            Ok(kubeconfig) => {
+                let context = options.context.clone().or_else(|| kubeconfig.current_context.clone()).unwrap_or_default();
+                (Config::from_custom_kubeconfig(kubeconfig, &options).await?, context)
+            }
+            // Running inside a pod with nothing asked for explicitly: use its service account
+            Err(_) if !self.selects_context() => (Config::infer().await?, "in-cluster".to_string()),
+            Err(e) => return Err(e.into()),
+        };
+        config.apply_debug_overrides();
+        if let Some(user) = &self.as_user {
+            config.auth_info.impersonate = Some(user.clone());
+            config.auth_info.impersonate_groups = (!self.as_groups.is_empty()).then(|| self.as_groups.clone());
+        }
+        self.network.apply(&mut config)?;
+        Ok((config, context))
+    }
+
+    /// Who requests are made as when impersonating, e.g. "alice (groups: dev, ops)".
+    pub fn impersonation(&self) -> Option<String> {
+        let user = self.as_user.as_ref()?;
+        match self.as_groups.is_empty() {
+            true => Some(user.clone()),
+            false => Some(format!("{} (groups: {})", user, self.as_groups.join(", "))),
+        }
+    }
+
+    /// Context names from the kubeconfig, in file order.
+    pub fn contexts(&self) -> Result<Vec<String>, KubeconfigError> {
+        Ok(self.read_kubeconfig()?.contexts.into_iter().map(|c| c.name).collect())
+    }
+
+    pub fn read_kubeconfig(&self) -> Result<Kubeconfig, KubeconfigError> {
+        match &self.kubeconfig {
+            Some(path) => Kubeconfig::read_from(path),
+            None => Kubeconfig::read(),
+        }
+    }
+}
+
+/// A client together with the context and default namespace it was built from,
+/// so every command resolves "the current namespace" the same way.
+#[derive(Clone)]
+pub struct Session {
+    pub client: Client,
+    pub context: String,
+    /// Used when a command is given no -n; the shell's `ns` can change it
+    pub namespace: String,
+    /// The context's own default namespace, restored by `ns -`
+    pub context_namespace: String,
+    pub connection: ConnectionArgs,
+    /// How this session's list/get calls are retried, from its --retries/--request-timeout
+    pub retry: RetryPolicy,
+}
+
+impl Session {
+    pub async fn connect(connection: ConnectionArgs) -> Result<Self, KlogError> {
+        let (config, context) = connection.config().await?;
+        let namespace = config.default_namespace.clone();
+        let client = Client::try_from(config)?;
+        let retry = RetryPolicy::from_args(&connection.network);
+        Ok(Self { client, context, context_namespace: namespace.clone(), namespace, connection, retry })
+    }
+}
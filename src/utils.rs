@@ -1,4 +1,7 @@
+use crate::error::KlogError;
 use crate::models::PodOption;
+use crate::rbac::{self, Permission};
+use crate::session::{ConnectionArgs, Session};
 use colored::*;
 use futures::future::join_all;
 use indicatif::{ProgressBar, ProgressStyle};
@@ -7,9 +10,8 @@ use inquire::ui::{Color, RenderConfig, StyleSheet, Styled};
 use k8s_openapi::api::apps::v1::Deployment;
 use k8s_openapi::api::core::v1::{Namespace, Pod};
 use kube::{
-    Api, Client,
+    Api,
     api::ListParams,
-    config::{Config},
 };
 use std::sync::Arc;
 use tokio::sync::Semaphore;
@@ -29,53 +31,118 @@ pub fn create_spinner(msg: &str) -> ProgressBar {
 
 // --- SHARED NAMESPACE LOGIC ---
 pub async fn get_selected_namespaces(
-    client: Client,
+    session: &Session,
     arg: Option<Option<String>>,
 ) -> Result<Vec<String>, Box<dyn std::error::Error + Send + Sync>> {
     match arg {
         None => {
-            let config = Config::infer().await?;
-            let current_ns = config.default_namespace.clone();
-            println!("Using context namespace: {}", current_ns.cyan());
+            let current_ns = session.namespace.clone();
+            let source = if current_ns == session.context_namespace { "context" } else { "shell" };
+            println!("Using {} namespace: {}", source, current_ns.cyan());
             Ok(vec![current_ns])
         }
         Some(None) => {
-            let pb = create_spinner("Fetching namespaces...");
-            let ns_api: Api<Namespace> = Api::all(client);
-            let ns_list = ns_api.list(&ListParams::default()).await?;
-            pb.finish_and_clear();
-
-            let ns_options: Vec<String> = ns_list
-                .items
-                .into_iter()
-                .filter_map(|n| n.metadata.name)
-                .collect();
+            let ns_options = list_namespaces(session).await?;
             Ok(MultiSelect::new("Select Namespaces:", ns_options).prompt()?)
         }
         Some(Some(ns)) => Ok(vec![ns]),
     }
 }
 
+/// Every namespace the user can pick from: the cluster's list, or the fallback
+/// namespaces when listing is forbidden.
+pub async fn list_namespaces(session: &Session) -> Result<Vec<String>, Box<dyn std::error::Error + Send + Sync>> {
+    let label = "Fetching namespaces...";
+    let pb = create_spinner(label);
+    let perm = Permission::new("list", "namespaces", None);
+    let listed = if rbac::missing(session, std::slice::from_ref(&perm)).await.is_empty() {
+        let ns_api: Api<Namespace> = Api::all(session.client.clone());
+        let lp = ListParams::default();
+        match session.retry.call(Some((&pb, label)), || ns_api.list(&lp)).await {
+            Ok(list) => Ok(Some(list.items.into_iter().filter_map(|n| n.metadata.name).collect())),
+            Err(kube::Error::Api(status)) if status.code == 403 => Ok(None),
+            Err(e) => Err(e),
+        }
+    } else {
+        Ok(None)
+    };
+    // Cleared before any error surfaces, or it would keep spinning over the message
+    pb.finish_and_clear();
+    let listed: Option<Vec<String>> = listed?;
+
+    // Many users may only see their own namespaces; offer the ones we know about instead
+    let names = match listed {
+        Some(names) => names,
+        None => {
+            let known = fallback_namespaces(&session.connection);
+            if known.is_empty() {
+                return Err(KlogError::Forbidden {
+                    verb: perm.verb.to_string(),
+                    resource: perm.resource.to_string(),
+                    namespace: None,
+                }
+                .into());
+            }
+            println!(
+                "{} missing permission `{}`, choosing from kubeconfig contexts and {}",
+                "⚠️ ".yellow(),
+                perm,
+                FALLBACK_NAMESPACES_ENV
+            );
+            known
+        }
+    };
+    Ok(names)
+}
+
+/// Comma separated namespaces offered when the cluster won't list them for us.
+pub const FALLBACK_NAMESPACES_ENV: &str = "KLOG_NAMESPACES";
+
+// Namespaces from $KLOG_NAMESPACES, then any named by a kubeconfig context
+fn fallback_namespaces(connection: &ConnectionArgs) -> Vec<String> {
+    let mut namespaces: Vec<String> = std::env::var(FALLBACK_NAMESPACES_ENV)
+        .unwrap_or_default()
+        .split(',')
+        .map(|ns| ns.trim().to_string())
+        .filter(|ns| !ns.is_empty())
+        .collect();
+    if let Ok(kubeconfig) = connection.read_kubeconfig() {
+        let from_contexts = kubeconfig.contexts.into_iter().filter_map(|c| c.context.and_then(|c| c.namespace));
+        for ns in from_contexts {
+            if !namespaces.contains(&ns) {
+                namespaces.push(ns);
+            }
+        }
+    }
+    namespaces
+}
+
 // --- SHARED POD FETCHING (PARALLEL) ---
 pub async fn fetch_all_pods(
-    client: Client,
+    session: &Session,
     namespaces: Vec<String>,
 ) -> Result<Vec<PodOption>, Box<dyn std::error::Error + Send + Sync>> {
-    let pb = create_spinner("Fetching pods...");
+    // Up to 8 lists share this spinner; a retry resets it to this label, not to what another list left on it
+    let label = "Fetching pods...";
+    let pb = create_spinner(label);
     let mut tasks = Vec::new();
     let semaphore = Arc::new(Semaphore::new(8));
 
     for ns in namespaces {
-        let c = client.clone();
+        let (c, retry) = (session.client.clone(), session.retry);
         let sem = semaphore.clone();
+        let pb = pb.clone();
         tasks.push(tokio::spawn(async move {
             let _permit = sem.acquire_owned().await.expect("semaphore closed");
             let api: Api<Pod> = Api::namespaced(c, &ns);
-            (ns, api.list(&ListParams::default()).await)
+            let lp = ListParams::default();
+            let list = retry.call(Some((&pb, label)), || api.list(&lp)).await;
+            (ns, list)
         }));
     }
 
     let results = join_all(tasks).await;
+    pb.finish_and_clear();
     let mut all_pods = Vec::new();
 
     for res in results {
@@ -90,23 +157,31 @@ pub async fn fetch_all_pods(
                 name,
                 namespace: ns.clone(),
                 containers,
+                cluster: None,
             });
         }
     }
-    pb.finish_and_clear();
     Ok(all_pods)
 }
 
 pub async fn fetch_all_deployments(
-    client: Client,
+    session: &Session,
     namespaces: Vec<String>,
 ) -> Result<Vec<String>, Box<dyn std::error::Error + Send + Sync>> {
-    let pb = create_spinner("Fetching deployments...");
+    let label = "Fetching deployments...";
+    let pb = create_spinner(label);
     let mut all_deploys = Vec::new();
+    let lp = ListParams::default();
 
     for ns in namespaces {
-        let api: Api<Deployment> = Api::namespaced(client.clone(), &ns);
-        let list = api.list(&ListParams::default()).await?;
+        let api: Api<Deployment> = Api::namespaced(session.client.clone(), &ns);
+        let list = match session.retry.call(Some((&pb, label)), || api.list(&lp)).await {
+            Ok(list) => list,
+            Err(e) => {
+                pb.finish_and_clear();
+                return Err(e.into());
+            }
+        };
         for d in list.items {
             if let Some(name) = d.metadata.name {
                 all_deploys.push(name);